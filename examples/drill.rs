@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  drill.rs - Drill file demo for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs::File;
+use std::io::BufReader;
+
+use clap::Parser;
+
+use pcbrepair::decoder::*;
+use pcbrepair::export::excellon;
+use pcbrepair::interpreter::*;
+use pcbrepair::parser::*;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The file to read.
+    file: String,
+
+    /// Write the non-plated holes instead of the plated ones.
+    #[arg(short, long)]
+    non_plated: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let file = match File::open(&args.file) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("Error opening file {:?}: {:?}", &args.file, e);
+            return;
+        }
+    };
+
+    let reader = BufReader::new(file);
+    let decoded = match DecodedPcbRepairFile::new(reader) {
+        Ok(pf) => pf,
+        Err(e) => {
+            eprintln!("Error decoding file {:?}: {:?}", &args.file, e);
+            return;
+        }
+    };
+
+    let parsed = match ParsedPcbRepairFile::from_decoded(&decoded) {
+        Ok(pf) => pf,
+        Err(error) => {
+            eprintln!("Error parsing file {:?}: {:?}", &args.file, error);
+            return;
+        }
+    };
+
+    let board = match Board::from_parsed(&parsed) {
+        Ok(board) => board,
+        Err(error) => {
+            eprintln!("Error interpreting file {:?}: {:?}", &args.file, error);
+            return;
+        }
+    };
+
+    let plating = if args.non_plated {
+        excellon::Plating::NonPlated
+    } else {
+        excellon::Plating::Plated
+    };
+    let holes = excellon::drill_holes(&board);
+    if let Err(error) = excellon::write_drill_file(&holes, plating, &mut std::io::stdout()) {
+        eprintln!("Error writing drill file: {:?}", error);
+    }
+}
@@ -36,6 +36,10 @@ use pcbrepair::parser::*;
 struct Args {
     /// The file to read.
     file: String,
+
+    /// Write one footprint per distinct package instead of one per component.
+    #[arg(short, long)]
+    library: bool,
 }
 
 fn main() {
@@ -66,14 +70,39 @@ fn main() {
         }
     };
 
-    let interpreted = match InterpretedPcbRepairFile::from_parsed(&parsed) {
-        Ok(pf) => pf,
+    let board = match Board::from_parsed(&parsed) {
+        Ok(b) => b,
         Err(error) => {
             eprintln!("Error interpreting file {:?}: {:?}", &args.file, error);
             return;
         }
     };
 
+    let footprints: Vec<(String, FootprintInfo, Option<Rect>)> = if args.library {
+        let library = Library::from_board(&board);
+        library
+            .footprints
+            .into_iter()
+            .map(|(name, info)| {
+                let bounds = library
+                    .components
+                    .iter()
+                    .find(|(_, r)| r.footprint == name)
+                    .and_then(|(refdes, _)| board.components[refdes].footprint_bounds);
+                (name, info, bounds)
+            })
+            .collect()
+    } else {
+        InterpretedPcbRepairFile::from_board(&board)
+            .footprints
+            .into_iter()
+            .map(|(name, info)| {
+                let bounds = board.components[&name].footprint_bounds;
+                (name, info, bounds)
+            })
+            .collect()
+    };
+
     // Create output directory based on input filename
     let base_name = Path::new(&args.file)
         .file_stem()
@@ -89,7 +118,7 @@ fn main() {
     }
 
     // Generate .kicad_mod files for each footprint
-    for (name, info) in &interpreted.footprints {
+    for (name, info, bounds) in &footprints {
         let mut content = String::new();
 
         // Write KiCad footprint header
@@ -117,22 +146,53 @@ fn main() {
 
         // Add pads for each pin
         for pin in &info.pins {
+            let shape = match pin.shape {
+                PadShape::Circle => "circle",
+                PadShape::Rect => "rect",
+                PadShape::RoundRect => "roundrect",
+                PadShape::Oval => "oval",
+            };
+            let (pad_type, drill, layers) = match (pin.pad_type, pin.drill_mm) {
+                (PadType::ThroughHole, Some(drill)) => (
+                    "thru_hole",
+                    format!(" (drill {})", drill.round_dp(6)),
+                    "*.Cu *.Mask",
+                ),
+                _ => ("smd", String::new(), "F.Cu F.Paste F.Mask"),
+            };
             content.push_str(&format!(
-                "  (pad \"{}\" smd circle (at {} {}) (size {} {}) (layers F.Cu F.Paste F.Mask)\n",
+                "  (pad \"{}\" {} {} (at {} {}) (size {} {}){} (layers {})\n",
                 pin.number,
+                pad_type,
+                shape,
                 pin.x_mm.round_dp(6),
                 pin.y_mm.round_dp(6),
-                pin.radius_mm.round_dp(6),
-                pin.radius_mm.round_dp(6)
+                pin.width_mm.round_dp(6),
+                pin.height_mm.round_dp(6),
+                drill,
+                layers
             ));
             content.push_str("  )\n");
         }
 
+        // Add a courtyard around the pads and the part outline
+        if let Some(bounds) = bounds {
+            let courtyard = bounds.expanded(COURTYARD_MARGIN_MM);
+            content.push_str(&format!(
+                "  (fp_rect (start {:.6} {:.6}) (end {:.6} {:.6}) (stroke (width 0.05) (type solid)) (fill none) (layer \"F.CrtYd\"))\n",
+                courtyard.min_x_mm, courtyard.min_y_mm, courtyard.max_x_mm, courtyard.max_y_mm
+            ));
+        }
+
         // Close the footprint
         content.push_str(")\n");
 
         // Write to file
-        let filename = format!("{}/{}.kicad_mod", output_dir.display(), name);
+        let filename = format!(
+            "{}/{}.kicad_mod",
+            output_dir.display(),
+            name.replace(['/', '\\'], "_")
+        );
         if let Err(e) = fs::write(&filename, content) {
             eprintln!("Failed to write file {}: {}", filename, e);
         }
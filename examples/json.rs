@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  json.rs - JSON export demo for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::fs::File;
+use std::io::BufReader;
+
+use clap::Parser;
+
+use pcbrepair::decoder::*;
+use pcbrepair::interpreter::*;
+use pcbrepair::parser::*;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The file to read.
+    file: String,
+
+    /// Also include the interpreted footprints.
+    #[arg(short, long)]
+    interpret: bool,
+
+    /// The text encoding of the file (e.g., "gbk", "big5", "shift-jis").
+    /// Detected automatically if not given.
+    #[arg(short, long)]
+    encoding: Option<TextEncoding>,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let file = match File::open(&args.file) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("Error opening file {:?}: {:?}", &args.file, e);
+            return;
+        }
+    };
+
+    let reader = BufReader::new(file);
+    let decoded = match DecodedPcbRepairFile::new(reader) {
+        Ok(pf) => pf,
+        Err(e) => {
+            eprintln!("Error decoding file {:?}: {:?}", &args.file, e);
+            return;
+        }
+    };
+
+    let parsed = match args.encoding {
+        Some(encoding) => ParsedPcbRepairFile::from_decoded_with_encoding(&decoded, encoding),
+        None => ParsedPcbRepairFile::from_decoded(&decoded),
+    };
+    let parsed = match parsed {
+        Ok(pf) => pf,
+        Err(error) => {
+            eprintln!("Error parsing file {:?}: {:?}", &args.file, error);
+            return;
+        }
+    };
+
+    let mut document = serde_json::json!({
+        "schema_version": pcbrepair::JSON_SCHEMA_VERSION,
+        "parsed": parsed,
+    });
+
+    if args.interpret {
+        let interpreted = match InterpretedPcbRepairFile::from_parsed(&parsed) {
+            Ok(pf) => pf,
+            Err(error) => {
+                eprintln!("Error interpreting file {:?}: {:?}", &args.file, error);
+                return;
+            }
+        };
+        document["interpreted"] = serde_json::json!(interpreted);
+    }
+
+    match serde_json::to_string_pretty(&document) {
+        Ok(json) => println!("{}", json),
+        Err(error) => eprintln!("Error serializing file {:?}: {:?}", &args.file, error),
+    }
+}
@@ -31,6 +31,11 @@ use pcbrepair::parser::*;
 struct Args {
     /// The file to read.
     file: String,
+
+    /// The text encoding of the file (e.g., "gbk", "big5", "shift-jis").
+    /// Detected automatically if not given.
+    #[arg(short, long)]
+    encoding: Option<TextEncoding>,
 }
 
 fn main() {
@@ -53,7 +58,11 @@ fn main() {
         }
     };
 
-    let parsed = match ParsedPcbRepairFile::from_decoded(&decoded) {
+    let parsed = match args.encoding {
+        Some(encoding) => ParsedPcbRepairFile::from_decoded_with_encoding(&decoded, encoding),
+        None => ParsedPcbRepairFile::from_decoded(&decoded),
+    };
+    let parsed = match parsed {
         Ok(pf) => pf,
         Err(error) => {
             eprintln!("Error parsing file {:?}: {:?}", &args.file, error);
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/export.rs - Exporters for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/*!
+ * # `export` Module
+ *
+ * This module provides functionality to write interpreted board data in
+ * formats that other tools can read.
+ *
+ * * [excellon]: Excellon drill files.
+ * * [graphviz]: Graphviz DOT graphs.
+ * * `json`: JSON documents (requires the `serde` feature).
+ *
+ * ## Usage Example
+ *
+ * ```no_run
+ * use std::fs::File;
+ * use std::io::BufReader;
+ *
+ * use pcbrepair::decoder::DecodedPcbRepairFile;
+ * use pcbrepair::export::graphviz;
+ * use pcbrepair::interpreter::{Board, Netlist, PowerTree};
+ * use pcbrepair::parser::ParsedPcbRepairFile;
+ *
+ * fn main() -> Result<(), Box<dyn std::error::Error>> {
+ *     let file = File::open("example.fz")?;
+ *     let decoded = DecodedPcbRepairFile::new(BufReader::new(file))?;
+ *     let parsed = ParsedPcbRepairFile::from_decoded(&decoded)?;
+ *
+ *     let board = Board::from_parsed(&parsed)?;
+ *     let tree = PowerTree::from_board(&board, &Netlist::from_board(&board));
+ *
+ *     graphviz::write_power_tree(&tree, &mut std::io::stdout())?;
+ *
+ *     Ok(())
+ * }
+ * ```
+ */
+
+pub mod excellon;
+pub mod graphviz;
+#[cfg(feature = "serde")]
+pub mod json;
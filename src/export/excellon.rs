@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/export/excellon.rs - Excellon drill exporter for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use rust_decimal::Decimal;
+
+use crate::interpreter::Board;
+use crate::interpreter::PadType;
+
+/// The drill diameter estimated for a via, as a fraction of its pad's
+/// diameter.
+const ESTIMATED_VIA_DRILL_RATIO: Decimal = Decimal::from_parts(5, 0, 0, false, 1);
+
+/// Whether a hole's walls are plated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Plating {
+    /// A plated hole, such as a via or a through-hole pin.
+    Plated,
+    /// A non-plated hole, such as a mounting hole.
+    NonPlated,
+}
+
+/// A hole to be drilled through the board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrillHole {
+    /// The X-coordinate on the board, in millimeters.
+    pub x_mm: Decimal,
+    /// The Y-coordinate on the board, in millimeters.
+    pub y_mm: Decimal,
+    /// The diameter of the hole in millimeters.
+    pub diameter_mm: Decimal,
+    /// Whether the hole is plated.
+    pub plating: Plating,
+}
+
+/// Collects the holes of a board from its through-hole pins and vias.
+///
+/// Through-hole pins use their estimated drill diameters, and are
+/// non-plated if the drill is at least as wide as the pad, since such a pad
+/// has no copper ring to plate. Vias are always plated, and since the file
+/// only gives the size of their pads, their drills are estimated as half the
+/// pad diameter. Pins and vias without a usable size are skipped.
+///
+/// # Arguments
+///
+/// * `board` - The board model.
+pub fn drill_holes(board: &Board) -> Vec<DrillHole> {
+    let mut holes = Vec::new();
+
+    for component in board.components.values() {
+        for pin in &component.pins {
+            let (PadType::ThroughHole, Some(diameter_mm)) = (pin.pad_type, pin.drill_mm) else {
+                continue;
+            };
+            let plating = if diameter_mm >= pin.width_mm.min(pin.height_mm) {
+                Plating::NonPlated
+            } else {
+                Plating::Plated
+            };
+            holes.push(DrillHole {
+                x_mm: pin.x_mm,
+                y_mm: pin.y_mm,
+                diameter_mm,
+                plating,
+            });
+        }
+    }
+
+    for via in &board.vias {
+        let Some(radius_mm) = via.radius_mm.filter(|r| *r > Decimal::ZERO) else {
+            continue;
+        };
+        holes.push(DrillHole {
+            x_mm: via.x_mm,
+            y_mm: via.y_mm,
+            diameter_mm: (radius_mm * Decimal::TWO * ESTIMATED_VIA_DRILL_RATIO).round_dp(2),
+            plating: Plating::Plated,
+        });
+    }
+
+    holes
+}
+
+/// Writes the holes of one plating type as an Excellon drill file.
+///
+/// The file uses metric units with explicit decimal points and absolute
+/// coordinates. Tools are numbered in order of increasing diameter, and
+/// holes are listed in the order they're given. Board shops usually want
+/// plated and non-plated holes in separate files, so this is called once
+/// for each.
+///
+/// # Arguments
+///
+/// * `holes` - The holes to write, e.g. from [drill_holes]. Holes of the
+///   other plating type are skipped.
+/// * `plating` - The plating type of the holes to write.
+/// * `writer` - The writer to write the drill file to.
+pub fn write_drill_file(
+    holes: &[DrillHole],
+    plating: Plating,
+    writer: &mut impl Write,
+) -> std::io::Result<()> {
+    let holes: Vec<&DrillHole> = holes.iter().filter(|h| h.plating == plating).collect();
+
+    // Round the diameters to the micrometer so that tiny differences don't
+    // each get their own tool
+    let mut tools: BTreeMap<Decimal, usize> = holes
+        .iter()
+        .map(|h| (h.diameter_mm.round_dp(3), 0))
+        .collect();
+    for (number, tool) in tools.values_mut().enumerate() {
+        *tool = number + 1;
+    }
+
+    writeln!(writer, "M48")?;
+    writeln!(writer, "; DRILL file generated by pcbrepair")?;
+    let kind = match plating {
+        Plating::Plated => "PTH",
+        Plating::NonPlated => "NPTH",
+    };
+    writeln!(writer, "; TYPE={}", kind)?;
+    writeln!(writer, "FMAT,2")?;
+    writeln!(writer, "METRIC")?;
+    for (diameter, tool) in &tools {
+        writeln!(writer, "T{}C{:.3}", tool, diameter)?;
+    }
+    writeln!(writer, "%")?;
+    writeln!(writer, "G90")?;
+    writeln!(writer, "G05")?;
+
+    for (diameter, tool) in &tools {
+        writeln!(writer, "T{}", tool)?;
+        for hole in holes
+            .iter()
+            .filter(|h| h.diameter_mm.round_dp(3) == *diameter)
+        {
+            writeln!(writer, "X{:.3}Y{:.3}", hole.x_mm, hole.y_mm)?;
+        }
+    }
+
+    writeln!(writer, "M30")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_drill_holes() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!J1!1!MTG!NO!0!\n\
+S!R1!1!R0402!NO!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!J1!1!1!0!0!!0.85!\n\
+S!B!J1!2!2!5!0!!1.6!\n\
+S!C!J1!3!3!10!0!!0.85!\n\
+S!A!R1!1!1!20!0!!0.25!\n\
+A!VIAID!NET_NAME!VIA_X!VIA_Y!RADIUS!\n\
+S!V1!A!30!0!0.3!\n\
+S!V2!A!31!0!0!\n\
+S!V3!A!32!0!!\n";
+        let mut board = board_from(content);
+
+        // A plated pin, a hole as wide as its pad, and a pin with no drill
+        let drills = [Some(Decimal::ONE), Some(Decimal::new(32, 1)), None];
+        for (pin, drill_mm) in board
+            .components
+            .get_mut("J1")
+            .unwrap()
+            .pins
+            .iter_mut()
+            .zip(drills)
+        {
+            pin.pad_type = PadType::ThroughHole;
+            pin.drill_mm = drill_mm;
+        }
+        // A drill on a surface-mount pin is ignored
+        let r1 = &mut board.components.get_mut("R1").unwrap().pins[0];
+        r1.pad_type = PadType::Smd;
+        r1.drill_mm = Some(Decimal::new(3, 1));
+
+        let hole = |x: i64, diameter_mm, plating| DrillHole {
+            x_mm: Decimal::new(x, 0),
+            y_mm: Decimal::ZERO,
+            diameter_mm,
+            plating,
+        };
+        assert_eq!(
+            drill_holes(&board),
+            [
+                hole(0, Decimal::ONE, Plating::Plated),
+                hole(5, Decimal::new(32, 1), Plating::NonPlated),
+                hole(30, Decimal::new(3, 1), Plating::Plated),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_drill_file() {
+        let hole = |x: i64, diameter: i64, plating| DrillHole {
+            x_mm: Decimal::new(x, 0),
+            y_mm: Decimal::new(-25, 1),
+            diameter_mm: Decimal::new(diameter, 1),
+            plating,
+        };
+        let holes = [
+            hole(3, 10, Plating::Plated),
+            hole(1, 4, Plating::Plated),
+            hole(2, 10, Plating::Plated),
+            hole(4, 32, Plating::NonPlated),
+        ];
+
+        let mut out = Vec::new();
+        write_drill_file(&holes, Plating::Plated, &mut out).unwrap();
+        let drl = String::from_utf8(out).unwrap();
+        assert!(drl.starts_with("M48\n"));
+        assert!(drl.contains("METRIC\nT1C0.400\nT2C1.000\n%\n"));
+        assert!(drl.contains("T1\nX1.000Y-2.500\nT2\nX3.000Y-2.500\nX2.000Y-2.500\n"));
+        assert!(!drl.contains("3.200"));
+        assert!(drl.ends_with("M30\n"));
+    }
+}
@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/export/graphviz.rs - Graphviz exporter for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::io::Write;
+
+use crate::interpreter::PowerTree;
+
+/// Quotes a string as a DOT ID.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Writes a power tree as a Graphviz DOT digraph.
+///
+/// Each rail is a node labeled with its name, voltage, and load count, and
+/// each edge points from a parent rail to a child rail and is labeled with
+/// the converter's reference designator.
+///
+/// # Arguments
+///
+/// * `tree` - The power tree to write.
+/// * `writer` - The writer to write the graph to.
+pub fn write_power_tree(tree: &PowerTree, writer: &mut impl Write) -> std::io::Result<()> {
+    writeln!(writer, "digraph power_tree {{")?;
+    writeln!(writer, "    rankdir=LR;")?;
+    writeln!(writer, "    node [shape=box];")?;
+
+    for rail in tree.rails.values() {
+        let mut label = rail.name.clone();
+        if let Some(voltage) = rail.voltage {
+            label.push_str(&format!("\n{} V", voltage));
+        }
+        label.push_str(&format!("\n{} loads", rail.load_count));
+        writeln!(
+            writer,
+            "    {} [label={}];",
+            quote(&rail.name),
+            quote(&label).replace('\n', "\\n")
+        )?;
+    }
+
+    for rail in tree.rails.values() {
+        let Some(parent) = &rail.parent else {
+            continue;
+        };
+        let converter = rail.converter.as_deref().unwrap_or("");
+        writeln!(
+            writer,
+            "    {} -> {} [label={}];",
+            quote(parent),
+            quote(&rail.name),
+            quote(converter)
+        )?;
+    }
+
+    writeln!(writer, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::PowerRail;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_write_power_tree() {
+        let rail = |name: &str, parent: Option<&str>| PowerRail {
+            name: name.into(),
+            voltage: Some(rust_decimal::Decimal::new(5, 0)),
+            power_state: None,
+            parent: parent.map(Into::into),
+            converter: parent.map(|_| "U1".into()),
+            load_count: 2,
+        };
+        let tree = PowerTree {
+            rails: BTreeMap::from([
+                ("+5V".into(), rail("+5V", None)),
+                ("+5V_\"A\"".into(), rail("+5V_\"A\"", Some("+5V"))),
+            ]),
+        };
+
+        let mut out = Vec::new();
+        write_power_tree(&tree, &mut out).unwrap();
+        let dot = String::from_utf8(out).unwrap();
+        assert!(dot.contains("    \"+5V\" [label=\"+5V\\n5 V\\n2 loads\"];\n"));
+        assert!(dot.contains("    \"+5V\" -> \"+5V_\\\"A\\\"\" [label=\"U1\"];\n"));
+    }
+}
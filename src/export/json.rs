@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/export/json.rs - JSON exporter for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::io::Write;
+
+use crate::interpreter::PowerTree;
+
+/// Writes a power tree as a pretty-printed JSON object.
+///
+/// # Arguments
+///
+/// * `tree` - The power tree to write.
+/// * `writer` - The writer to write the JSON to.
+pub fn write_power_tree(tree: &PowerTree, writer: &mut impl Write) -> std::io::Result<()> {
+    serde_json::to_writer_pretty(&mut *writer, tree)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::PowerRail;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_write_power_tree() {
+        let rail = |name: &str, parent: Option<&str>| PowerRail {
+            name: name.into(),
+            voltage: Some(rust_decimal::Decimal::new(5, 0)),
+            power_state: None,
+            parent: parent.map(Into::into),
+            converter: parent.map(|_| "U1".into()),
+            load_count: 2,
+        };
+        let tree = PowerTree {
+            rails: BTreeMap::from([
+                ("+5V".into(), rail("+5V", None)),
+                ("+5VS".into(), rail("+5VS", Some("+5V"))),
+            ]),
+        };
+
+        let mut out = Vec::new();
+        write_power_tree(&tree, &mut out).unwrap();
+        assert!(out.ends_with(b"}\n"));
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let rails = json["rails"].as_object().unwrap();
+        assert_eq!(rails.keys().collect::<Vec<_>>(), ["+5V", "+5VS"]);
+        assert_eq!(rails["+5V"]["parent"], serde_json::Value::Null);
+        assert_eq!(rails["+5VS"]["parent"], "+5V");
+        assert_eq!(rails["+5VS"]["converter"], "U1");
+        assert_eq!(rails["+5VS"]["load_count"], 2);
+    }
+}
@@ -64,13 +64,47 @@
 use std::collections::HashMap;
 
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 
-use crate::parser::Component;
 use crate::parser::ParsedPcbRepairFile;
-use crate::parser::Units;
+
+mod board;
+mod classify;
+mod connectivity;
+mod graphics;
+mod library;
+mod netinfo;
+mod netlist;
+mod outline;
+mod pads;
+mod power;
+mod shapes;
+mod spatial;
+mod stackup;
+mod text;
+mod traces;
+mod verify;
+
+pub use board::*;
+pub use classify::*;
+pub use connectivity::*;
+pub use graphics::*;
+pub use library::*;
+pub use netinfo::*;
+pub use netlist::*;
+pub use outline::*;
+pub use pads::*;
+pub use power::*;
+pub use shapes::*;
+pub use spatial::*;
+pub use stackup::*;
+pub use text::*;
+pub use traces::*;
+pub use verify::*;
 
 /// Represents a pin in a footprint.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pin {
     /// The name of the pin.
     pub name: String,
@@ -82,50 +116,91 @@ pub struct Pin {
     pub y_mm: Decimal,
     /// The radius of the pin in millimeters.
     pub radius_mm: Decimal,
+    /// The shape of the pin's pad.
+    pub shape: PadShape,
+    /// The width of the pin's pad in millimeters.
+    pub width_mm: Decimal,
+    /// The height of the pin's pad in millimeters.
+    pub height_mm: Decimal,
+    /// Whether the pad's size was inferred from the pin pitch because the
+    /// file's was missing or implausible.
+    pub size_inferred: bool,
+    /// How the pin's pad is mounted.
+    pub pad_type: PadType,
+    /// The estimated drill diameter of a through-hole pin, in millimeters.
+    pub drill_mm: Option<Decimal>,
 }
 
-/// Information about a footprint, including its pins.
-#[derive(Debug)]
-pub struct FootprintInfo {
-    /// List of pins in the footprint.
-    pub pins: Vec<Pin>,
-    /// The description of the footprint.
-    pub description: String,
-}
-
-struct ComponentMap {
-    descriptions: Vec<String>,
-    index_map: HashMap<String, usize>,
+/// The placement transform of a footprint on the board.
+///
+/// A point in footprint coordinates is mapped onto the board by first
+/// negating its X-coordinate if [Transform::mirrored] is set, then rotating
+/// it counter-clockwise by [Transform::rotation] degrees, and finally
+/// translating it to the component's origin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    /// The counter-clockwise rotation in degrees.
+    pub rotation: u16,
+    /// Whether the footprint is mirrored about its Y-axis.
+    pub mirrored: bool,
 }
 
-impl ComponentMap {
-    fn new(components: &[Component]) -> Self {
-        let mut descriptions = Vec::new();
-        let mut index_map = HashMap::new();
+impl Transform {
+    /// Maps a point from footprint coordinates to board coordinates relative
+    /// to the component's origin.
+    pub fn apply(&self, x: Decimal, y: Decimal) -> (Decimal, Decimal) {
+        let x = if self.mirrored { -x } else { x };
+        rotate(x, y, i32::from(self.rotation))
+    }
 
-        for component in components {
-            let index = descriptions.len();
-            descriptions.push(component.description.trim().to_string());
-            for refdes in &component.location {
-                index_map.insert(refdes.clone(), index);
-            }
-        }
+    /// Maps a point from board coordinates relative to the component's
+    /// origin to footprint coordinates. This is the inverse of
+    /// [Transform::apply].
+    pub fn invert(&self, x: Decimal, y: Decimal) -> (Decimal, Decimal) {
+        let (x, y) = rotate(x, y, -i32::from(self.rotation));
+        if self.mirrored { (-x, y) } else { (x, y) }
+    }
+}
 
-        Self {
-            descriptions,
-            index_map,
+/// Rotates a point counter-clockwise around the origin. Multiples of 90
+/// degrees are exact, and other angles are rounded to the nearest nanometer.
+pub(crate) fn rotate(x: Decimal, y: Decimal, degrees: i32) -> (Decimal, Decimal) {
+    match degrees.rem_euclid(360) {
+        0 => (x, y),
+        90 => (-y, x),
+        180 => (-x, -y),
+        270 => (y, -x),
+        degrees => {
+            let (sin, cos) = f64::from(degrees).to_radians().sin_cos();
+            let xf = x.to_f64().unwrap_or_default();
+            let yf = y.to_f64().unwrap_or_default();
+            let to_decimal = |v: f64| Decimal::from_f64_retain(v).unwrap_or_default().round_dp(6);
+            (
+                to_decimal(xf * cos - yf * sin),
+                to_decimal(xf * sin + yf * cos),
+            )
         }
     }
+}
 
-    fn get(&self, refdes: &str) -> Option<&str> {
-        self.index_map
-            .get(refdes)
-            .map(|&idx| self.descriptions[idx].as_str())
-    }
+/// Information about a footprint, including its pins.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootprintInfo {
+    /// List of pins in the footprint.
+    pub pins: Vec<Pin>,
+    /// The description of the footprint.
+    pub description: String,
+    /// The transform that was removed from the pins to bring the footprint
+    /// into its library orientation. Applying it maps the pins back onto the
+    /// board.
+    pub transform: Transform,
 }
 
 /// A fully interpreted PCB repair file, containing footprint data.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterpretedPcbRepairFile {
     /// A map of footprint names to their associated pin information.
     pub footprints: HashMap<String, FootprintInfo>,
@@ -134,7 +209,11 @@ pub struct InterpretedPcbRepairFile {
 impl InterpretedPcbRepairFile {
     /// Converts a parsed PCB file into an interpreted format.
     ///
-    /// This includes unit conversion and centering of footprint pins.
+    /// This includes unit conversion and centering of footprint pins. Each
+    /// footprint is also brought into its library orientation by undoing its
+    /// component's rotation and mirroring, as with
+    /// [InterpretedPcbRepairFile::from_board], so pads rotated by a quarter
+    /// turn have their width and height swapped.
     ///
     /// # Arguments
     ///
@@ -144,98 +223,119 @@ impl InterpretedPcbRepairFile {
     ///
     /// A `Result` containing the interpreted file or an error.
     pub fn from_parsed(parsed: &ParsedPcbRepairFile) -> Result<Self, Box<dyn std::error::Error>> {
-        let mm_per_mil: Decimal = Decimal::new(254, 4);
-
-        let content = &parsed.content;
-
-        let mut footprint_pins = HashMap::new();
-
-        for board_pin in &content.pins {
-            let fp_name = board_pin.refdes.clone();
-
-            // Fixup invalid pin numbers
-            let pin_number = match board_pin.pin_number.as_str() {
-                "" => board_pin.pin_name.clone(),
-                "0" => board_pin.pin_name.clone(),
-                _ => board_pin.pin_number.clone(),
-            };
-
-            // Use a more descriptive name
-            let pin_name = if pin_number != board_pin.pin_name {
-                board_pin.pin_name.clone()
-            } else {
-                board_pin.net_name.clone()
-            };
-
-            // Convert coordinates to millimeters
-            let x = match content.units {
-                Units::Mils => board_pin.pin_x * mm_per_mil,
-                Units::Millimeters => board_pin.pin_x,
-            };
-            let y = match content.units {
-                Units::Mils => board_pin.pin_y * mm_per_mil,
-                Units::Millimeters => board_pin.pin_y,
-            };
-
-            let radius = match content.units {
-                Units::Mils => board_pin.radius * mm_per_mil,
-                Units::Millimeters => board_pin.radius,
-            };
-
-            let pin = Pin {
-                name: pin_name,
-                number: pin_number,
-                x_mm: x,
-                y_mm: y,
-                radius_mm: radius,
-            };
-
-            footprint_pins
-                .entry(fp_name)
-                .or_insert_with(Vec::new)
-                .push(pin);
-        }
-
-        let components = ComponentMap::new(&parsed.description.components);
+        let board = Board::from_parsed(parsed)?;
+        Ok(Self::from_board(&board))
+    }
 
+    /// Derives footprints from a board model, with each footprint's pins
+    /// centered around its component's origin and un-rotated and un-mirrored
+    /// into its library orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    pub fn from_board(board: &Board) -> Self {
         let mut footprints = HashMap::new();
 
-        // Center each footprint's pins around (0, 0)
-        for (fp_name, pins) in footprint_pins {
-            if pins.is_empty() {
+        for (fp_name, component) in &board.components {
+            if component.pins.is_empty() {
                 continue;
             }
 
-            let total_x: Decimal = pins.iter().map(|p| p.x_mm).sum();
-            let total_y: Decimal = pins.iter().map(|p| p.y_mm).sum();
-            let pin_count = Decimal::new(pins.len().try_into()?, 0);
-            let avg_x = total_x / pin_count;
-            let avg_y = total_y / pin_count;
-
-            let centered_pins: Vec<Pin> = pins
-                .into_iter()
-                .map(|mut p| {
-                    p.x_mm -= avg_x;
-                    p.y_mm -= avg_y;
-                    p
+            let transform = Transform {
+                rotation: component.rotation,
+                mirrored: component.mirrored,
+            };
+
+            // Center each footprint's pins around (0, 0) and undo the
+            // component's rotation and mirroring
+            let centered_pins: Vec<Pin> = component
+                .pins
+                .iter()
+                .map(|p| {
+                    let (x_mm, y_mm) = transform.invert(
+                        p.x_mm - component.origin_x_mm,
+                        p.y_mm - component.origin_y_mm,
+                    );
+                    // Pad sizes are axis-aligned on the board, so they only
+                    // need to be swapped back for quarter turns
+                    let (width_mm, height_mm) = if component.rotation % 180 == 90 {
+                        (p.height_mm, p.width_mm)
+                    } else {
+                        (p.width_mm, p.height_mm)
+                    };
+                    Pin {
+                        name: p.name.clone(),
+                        number: p.number.clone(),
+                        x_mm,
+                        y_mm,
+                        radius_mm: p.radius_mm,
+                        shape: p.shape,
+                        width_mm,
+                        height_mm,
+                        size_inferred: p.size_inferred,
+                        pad_type: p.pad_type,
+                        drill_mm: p.drill_mm,
+                    }
                 })
                 .collect();
 
-            let description = if let Some(d) = components.get(&fp_name) {
-                d.to_string()
-            } else {
-                String::new()
-            };
-
             footprints.insert(
-                fp_name,
+                fp_name.clone(),
                 FootprintInfo {
                     pins: centered_pins,
-                    description,
+                    description: component.description.clone(),
+                    transform,
                 },
             );
         }
 
-        Ok(Self { footprints })
+        Self { footprints }
+    }
+}
+
+/// Builds a board from test content, with a description that lists no
+/// components.
+#[cfg(test)]
+pub(crate) fn board_from(content: &[u8]) -> Board {
+    board_with_description(content, b"B|1|B|1|PN\n")
+}
+
+/// Builds a board from test content and description.
+#[cfg(test)]
+pub(crate) fn board_with_description(content: &[u8], description: &[u8]) -> Board {
+    use crate::parser::{Content, Description, ParsedPcbRepairFile};
+
+    let parsed = ParsedPcbRepairFile {
+        content: Content::from_bytes(content).unwrap(),
+        description: Description::from_bytes(description).unwrap(),
+    };
+    Board::from_parsed(&parsed).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_round_trip() {
+        let (x, y) = (Decimal::new(15, 1), Decimal::new(-4, 1));
+        for rotation in [0, 90, 180, 270, 45] {
+            for mirrored in [false, true] {
+                let transform = Transform { rotation, mirrored };
+                let (bx, by) = transform.apply(x, y);
+                let (fx, fy) = transform.invert(bx, by);
+                assert_eq!((fx.round_dp(6), fy.round_dp(6)), (x, y));
+            }
+        }
+
+        let transform = Transform {
+            rotation: 90,
+            mirrored: true,
+        };
+        assert_eq!(
+            transform.apply(Decimal::ONE, Decimal::ZERO),
+            (Decimal::ZERO, -Decimal::ONE)
+        );
     }
 }
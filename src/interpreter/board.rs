@@ -0,0 +1,768 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/board.rs - Board placement model for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::parser::Component;
+use crate::parser::ParsedPcbRepairFile;
+use crate::parser::Units;
+
+use super::ComponentKind;
+use super::PadShape;
+use super::PadType;
+use super::PassiveValue;
+use super::Primitive;
+use super::Rect;
+use super::TextItem;
+use super::pads::PadIndex;
+use super::pads::infer_pad_shapes;
+use super::pads::infer_pad_sizes;
+use super::pads::infer_pad_types;
+
+/// The side of the board a component is placed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    /// The top (component) side.
+    Top,
+    /// The bottom (solder) side.
+    Bottom,
+}
+
+/// Represents a pin of a placed component, in board coordinates.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardPin {
+    /// The name of the pin.
+    pub name: String,
+    /// The number of the pin.
+    pub number: String,
+    /// The name of the net this pin is connected to.
+    pub net_name: String,
+    /// The X-coordinate on the board, in millimeters.
+    pub x_mm: Decimal,
+    /// The Y-coordinate on the board, in millimeters.
+    pub y_mm: Decimal,
+    /// The radius of the pin in millimeters.
+    pub radius_mm: Decimal,
+    /// The shape of the pin's pad.
+    pub shape: PadShape,
+    /// The width of the pin's pad on the board, in millimeters.
+    pub width_mm: Decimal,
+    /// The height of the pin's pad on the board, in millimeters.
+    pub height_mm: Decimal,
+    /// Whether the pad's size was inferred from the pin pitch because the
+    /// file's was missing or implausible.
+    pub size_inferred: bool,
+    /// How the pin's pad is mounted.
+    pub pad_type: PadType,
+    /// The estimated drill diameter of a through-hole pin, in millimeters.
+    pub drill_mm: Option<Decimal>,
+}
+
+/// The clearance around a component's body and pads that its estimated
+/// courtyard adds, in millimeters.
+pub const COURTYARD_MARGIN_MM: f64 = 0.25;
+
+/// A component placed on the board.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlacedComponent {
+    /// The reference designator (e.g., "U1") of the component.
+    pub refdes: String,
+    /// The name of the component's symbol, or an empty string if the file
+    /// has no symbol for it.
+    pub sym_name: String,
+    /// The X-coordinate of the component's origin on the board, in
+    /// millimeters. This is the centroid of its pins, or zero if it has none.
+    pub origin_x_mm: Decimal,
+    /// The Y-coordinate of the component's origin on the board, in
+    /// millimeters. This is the centroid of its pins, or zero if it has none.
+    pub origin_y_mm: Decimal,
+    /// The insertion code of the component's symbol, if it has one.
+    pub insertion_code: Option<u64>,
+    /// The rotation of the component in degrees.
+    pub rotation: u16,
+    /// Whether the component is mirrored.
+    pub mirrored: bool,
+    /// The side of the board the component is placed on.
+    pub side: Side,
+    /// The component's pins.
+    pub pins: Vec<BoardPin>,
+    /// The index of the component's entry in
+    /// [Description::components](crate::parser::Description::components), if
+    /// it has one.
+    pub bom_index: Option<usize>,
+    /// The description of the component from the BOM, or an empty string if
+    /// it has none.
+    pub description: String,
+    /// The kind of component, from its description and reference designator.
+    pub kind: ComponentKind,
+    /// The value and ratings of the component, if it's a passive component.
+    pub value: Option<PassiveValue>,
+    /// The axis-aligned bounding box of the component on the board.
+    pub bounds: Option<Rect>,
+    /// The bounding box of the component in footprint coordinates, i.e.
+    /// relative to its origin and with its rotation and mirroring removed.
+    pub footprint_bounds: Option<Rect>,
+    /// The bounding box of the component aligned with its rotation.
+    pub oriented_bounds: Option<OrientedRect>,
+    /// The estimated courtyard of the component: its oriented bounding box
+    /// grown by [COURTYARD_MARGIN_MM] on every side.
+    pub courtyard: Option<OrientedRect>,
+}
+
+/// A rectangle rotated about its center, in board coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrientedRect {
+    /// The X-coordinate of the center, in millimeters.
+    pub center_x_mm: f64,
+    /// The Y-coordinate of the center, in millimeters.
+    pub center_y_mm: f64,
+    /// The width before rotation, in millimeters.
+    pub width_mm: f64,
+    /// The height before rotation, in millimeters.
+    pub height_mm: f64,
+    /// The counter-clockwise rotation in degrees.
+    pub rotation: f64,
+}
+
+impl OrientedRect {
+    /// Returns the rectangle grown by `margin_mm` on every side, e.g. to
+    /// estimate a courtyard.
+    pub fn expanded(&self, margin_mm: f64) -> Self {
+        Self {
+            width_mm: self.width_mm + 2.0 * margin_mm,
+            height_mm: self.height_mm + 2.0 * margin_mm,
+            ..*self
+        }
+    }
+
+    /// Returns the corners of the rectangle, counter-clockwise.
+    pub fn corners(&self) -> [(f64, f64); 4] {
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (w, h) = (self.width_mm / 2.0, self.height_mm / 2.0);
+        [(-w, -h), (w, -h), (w, h), (-w, h)].map(|(x, y)| {
+            (
+                self.center_x_mm + x * cos - y * sin,
+                self.center_y_mm + x * sin + y * cos,
+            )
+        })
+    }
+}
+
+/// A graphic record from the file, decoded into a [Primitive].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardGraphic {
+    /// The class of the record (e.g., "ETCH" or "BOARD GEOMETRY"), or an
+    /// empty string for symbol graphics, which have no class.
+    pub class: String,
+    /// The subclass of the record (e.g., "TOP" or "ASSEMBLY_TOP").
+    pub subclass: String,
+    /// The record's `GRAPHIC_DATA_NUMBER`, shared by the records of one
+    /// figure.
+    pub number: u64,
+    /// The record's `RECORD_TAG`.
+    pub record_tag: String,
+    /// The reference designator of the component the record belongs to, or
+    /// an empty string.
+    pub refdes: String,
+    /// The name of the symbol the record belongs to, or an empty string.
+    pub sym_name: String,
+    /// The name of the net the record is on, or an empty string.
+    pub net_name: String,
+    /// The decoded shape.
+    pub primitive: Primitive,
+}
+
+/// A test via on the board.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardTestVia {
+    /// The name of the test via.
+    pub name: String,
+    /// The name of the net this test via is connected to.
+    pub net_name: String,
+    /// The reference designator of the component the test via belongs to, if
+    /// any.
+    pub refdes: String,
+    /// The number of the pin the test via belongs to, if any.
+    pub pin_number: String,
+    /// The X-coordinate on the board, in millimeters.
+    pub x_mm: Decimal,
+    /// The Y-coordinate on the board, in millimeters.
+    pub y_mm: Decimal,
+    /// The radius of the test via in millimeters.
+    pub radius_mm: Decimal,
+}
+
+/// A via on the board.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardVia {
+    /// The ID of the via.
+    pub id: String,
+    /// The name of the net this via is connected to.
+    pub net_name: String,
+    /// The X-coordinate on the board, in millimeters.
+    pub x_mm: Decimal,
+    /// The Y-coordinate on the board, in millimeters.
+    pub y_mm: Decimal,
+    /// The radius of the via in millimeters, if known.
+    pub radius_mm: Option<Decimal>,
+    /// The name of the layer the via starts on, if known.
+    pub start_layer: String,
+    /// The name of the layer the via ends on, if known.
+    pub end_layer: String,
+}
+
+/// The placement of everything on the board, in millimeters.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Board {
+    /// The placed components, by reference designator.
+    pub components: BTreeMap<String, PlacedComponent>,
+    /// The test vias on the board.
+    pub testvias: Vec<BoardTestVia>,
+    /// The vias on the board.
+    pub vias: Vec<BoardVia>,
+    /// The decoded graphic data records, symbol graphics first. Records of
+    /// unknown kinds are skipped.
+    pub graphics: Vec<BoardGraphic>,
+    /// The text strings on the board, in the order of their records.
+    pub texts: Vec<TextItem>,
+}
+
+struct ComponentMap {
+    descriptions: Vec<String>,
+    index_map: HashMap<String, usize>,
+}
+
+impl ComponentMap {
+    fn new(components: &[Component]) -> Self {
+        let mut descriptions = Vec::new();
+        let mut index_map = HashMap::new();
+
+        for component in components {
+            let index = descriptions.len();
+            descriptions.push(component.description.trim().to_string());
+            for refdes in &component.location {
+                index_map.insert(refdes.clone(), index);
+            }
+        }
+
+        Self {
+            descriptions,
+            index_map,
+        }
+    }
+
+    fn get(&self, refdes: &str) -> Option<(usize, &str)> {
+        self.index_map
+            .get(refdes)
+            .map(|&idx| (idx, self.descriptions[idx].as_str()))
+    }
+}
+
+/// Converts a length in the file's units to millimeters.
+pub(crate) fn to_mm(units: &Units, value: Decimal) -> Decimal {
+    let mm_per_mil: Decimal = Decimal::new(254, 4);
+    match units {
+        Units::Mils => value * mm_per_mil,
+        Units::Millimeters => value,
+    }
+}
+
+/// Returns whether a symbol graphic outlines the body of its component.
+fn is_outline_subclass(subclass: &str) -> bool {
+    let upper = subclass.to_ascii_uppercase();
+    ["ASSEMBLY", "PLACE_BOUND", "DFA_BOUND", "PACKAGE"]
+        .iter()
+        .any(|s| upper.starts_with(s))
+}
+
+/// Maps a disc from board coordinates to a component's footprint
+/// coordinates, undoing its rotation and then its mirroring.
+fn disc_to_footprint(component: &PlacedComponent, (x, y, r): (f64, f64, f64)) -> (f64, f64, f64) {
+    let f = |value: Decimal| value.to_f64().unwrap_or(0.0);
+    let (sin, cos) = f64::from(component.rotation).to_radians().sin_cos();
+    let mirror = if component.mirrored { -1.0 } else { 1.0 };
+    let (dx, dy) = (x - f(component.origin_x_mm), y - f(component.origin_y_mm));
+    (mirror * (dx * cos + dy * sin), -dx * sin + dy * cos, r)
+}
+
+/// Maps a disc from a component's footprint coordinates to board
+/// coordinates. This is the inverse of [disc_to_footprint].
+fn disc_to_board(component: &PlacedComponent, (x, y, r): (f64, f64, f64)) -> (f64, f64, f64) {
+    let f = |value: Decimal| value.to_f64().unwrap_or(0.0);
+    let (sin, cos) = f64::from(component.rotation).to_radians().sin_cos();
+    let x = if component.mirrored { -x } else { x };
+    (
+        f(component.origin_x_mm) + x * cos - y * sin,
+        f(component.origin_y_mm) + x * sin + y * cos,
+        r,
+    )
+}
+
+/// Collects the outline graphics that are shared by every component with the
+/// same symbol, in footprint coordinates, by symbol name.
+///
+/// Symbol graphics without a reference designator are given once per symbol,
+/// at the coordinates of one of its placements. That placement is taken to be
+/// the component with the symbol whose origin is inside the graphics and
+/// nearest their center, and its transform is undone so that the outline can
+/// be placed on every component with the symbol. Graphics that don't surround
+/// any such component are skipped.
+fn symbol_outlines(
+    components: &BTreeMap<String, PlacedComponent>,
+    graphics: &[BoardGraphic],
+) -> BTreeMap<String, Vec<(f64, f64, f64)>> {
+    let f = |value: Decimal| value.to_f64().unwrap_or(0.0);
+
+    let mut discs: BTreeMap<&str, Vec<(f64, f64, f64)>> = BTreeMap::new();
+    for graphic in graphics {
+        if graphic.refdes.is_empty()
+            && !graphic.sym_name.is_empty()
+            && is_outline_subclass(&graphic.subclass)
+        {
+            discs
+                .entry(graphic.sym_name.as_str())
+                .or_default()
+                .extend(graphic.primitive.extent_discs());
+        }
+    }
+
+    discs
+        .into_iter()
+        .filter_map(|(sym_name, discs)| {
+            let bounds = Rect::from_discs(discs.iter().copied())?;
+            let (cx, cy) = bounds.center();
+            let distance =
+                |c: &PlacedComponent| (f(c.origin_x_mm) - cx).hypot(f(c.origin_y_mm) - cy);
+            let reference = components
+                .values()
+                .filter(|c| c.sym_name == sym_name && !c.pins.is_empty())
+                .filter(|c| bounds.contains(f(c.origin_x_mm), f(c.origin_y_mm)))
+                .min_by(|a, b| distance(a).total_cmp(&distance(b)))?;
+            let local = discs
+                .into_iter()
+                .map(|disc| disc_to_footprint(reference, disc))
+                .collect();
+            Some((sym_name.to_string(), local))
+        })
+        .collect()
+}
+
+/// Computes the bounding boxes and courtyard of a component from its pads
+/// and the outline graphics that belong to it.
+///
+/// # Arguments
+///
+/// * `component` - The component.
+/// * `graphics` - The board's graphics. Those with the component's reference
+///   designator are used as they are.
+/// * `symbol_outline` - The outline shared by every component with the
+///   component's symbol, in footprint coordinates, from [symbol_outlines].
+fn compute_bounds(
+    component: &mut PlacedComponent,
+    graphics: &[BoardGraphic],
+    symbol_outline: &[(f64, f64, f64)],
+) {
+    let f = |value: Decimal| value.to_f64().unwrap_or(0.0);
+
+    let mut discs: Vec<(f64, f64, f64)> = Vec::new();
+    for pin in &component.pins {
+        let (x, y) = (f(pin.x_mm), f(pin.y_mm));
+        let (half_w, half_h) = (f(pin.width_mm) / 2.0, f(pin.height_mm) / 2.0);
+        if pin.shape == PadShape::Circle {
+            discs.push((x, y, half_w.max(half_h)));
+        } else {
+            // The corners of the pad
+            for (sx, sy) in [(-1.0, -1.0), (-1.0, 1.0), (1.0, -1.0), (1.0, 1.0)] {
+                discs.push((x + sx * half_w, y + sy * half_h, 0.0));
+            }
+        }
+    }
+    for graphic in graphics {
+        let owned = !graphic.refdes.is_empty() && graphic.refdes == component.refdes;
+        if owned && is_outline_subclass(&graphic.subclass) {
+            discs.extend(graphic.primitive.extent_discs());
+        }
+    }
+    discs.extend(
+        symbol_outline
+            .iter()
+            .map(|&disc| disc_to_board(component, disc)),
+    );
+    if discs.is_empty() {
+        return;
+    }
+
+    component.bounds = Rect::from_discs(discs.iter().copied());
+    component.footprint_bounds =
+        Rect::from_discs(discs.iter().map(|&disc| disc_to_footprint(component, disc)));
+    component.oriented_bounds = component.footprint_bounds.map(|local| {
+        let (cx, cy) = local.center();
+        let (center_x_mm, center_y_mm, _) = disc_to_board(component, (cx, cy, 0.0));
+        OrientedRect {
+            center_x_mm,
+            center_y_mm,
+            width_mm: local.width_mm(),
+            height_mm: local.height_mm(),
+            rotation: f64::from(component.rotation),
+        }
+    });
+    component.courtyard = component
+        .oriented_bounds
+        .map(|bounds| bounds.expanded(COURTYARD_MARGIN_MM));
+}
+
+impl Board {
+    /// Builds the board model from a parsed PCB file.
+    ///
+    /// Every component that has a symbol or at least one pin is included,
+    /// with its pins converted to millimeters.
+    ///
+    /// # Arguments
+    ///
+    /// * `parsed` - The parsed PCB file data.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the board or an error.
+    pub fn from_parsed(parsed: &ParsedPcbRepairFile) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = &parsed.content;
+        let bom = ComponentMap::new(&parsed.description.components);
+
+        let mut components = BTreeMap::new();
+
+        let new_component = |refdes: &str| {
+            let (bom_index, description) = match bom.get(refdes) {
+                Some((index, description)) => (Some(index), description.to_string()),
+                None => (None, String::new()),
+            };
+            let kind = ComponentKind::classify(refdes, &description);
+            PlacedComponent {
+                refdes: refdes.to_string(),
+                sym_name: String::new(),
+                origin_x_mm: Decimal::ZERO,
+                origin_y_mm: Decimal::ZERO,
+                insertion_code: None,
+                rotation: 0,
+                mirrored: false,
+                side: Side::Top,
+                pins: Vec::new(),
+                bom_index,
+                value: PassiveValue::parse(kind, &description),
+                description,
+                kind,
+                bounds: None,
+                footprint_bounds: None,
+                oriented_bounds: None,
+                courtyard: None,
+            }
+        };
+
+        for symbol in &content.symbols {
+            let component = components
+                .entry(symbol.refdes.clone())
+                .or_insert_with(|| new_component(&symbol.refdes));
+            component.sym_name = symbol.sym_name.clone();
+            component.insertion_code = Some(symbol.comp_insertion_code);
+            component.rotation = symbol.sym_rotate;
+            component.mirrored = symbol.sym_mirror;
+            component.side = if symbol.sym_mirror {
+                Side::Bottom
+            } else {
+                Side::Top
+            };
+        }
+
+        for board_pin in &content.pins {
+            // Fixup invalid pin numbers
+            let pin_number = match board_pin.pin_number.as_str() {
+                "" => board_pin.pin_name.clone(),
+                "0" => board_pin.pin_name.clone(),
+                _ => board_pin.pin_number.clone(),
+            };
+
+            // Use a more descriptive name
+            let pin_name = if pin_number != board_pin.pin_name {
+                board_pin.pin_name.clone()
+            } else {
+                board_pin.net_name.clone()
+            };
+
+            // Assume a circular pad until the graphics say otherwise
+            let radius_mm = to_mm(&content.units, board_pin.radius);
+            let pin = BoardPin {
+                name: pin_name,
+                number: pin_number,
+                net_name: board_pin.net_name.clone(),
+                x_mm: to_mm(&content.units, board_pin.pin_x),
+                y_mm: to_mm(&content.units, board_pin.pin_y),
+                radius_mm,
+                shape: PadShape::Circle,
+                width_mm: radius_mm * Decimal::TWO,
+                height_mm: radius_mm * Decimal::TWO,
+                size_inferred: false,
+                pad_type: PadType::Smd,
+                drill_mm: None,
+            };
+
+            components
+                .entry(board_pin.refdes.clone())
+                .or_insert_with(|| new_component(&board_pin.refdes))
+                .pins
+                .push(pin);
+        }
+
+        // Use the centroid of each component's pins as its origin
+        for component in components.values_mut() {
+            if component.pins.is_empty() {
+                continue;
+            }
+
+            let total_x: Decimal = component.pins.iter().map(|p| p.x_mm).sum();
+            let total_y: Decimal = component.pins.iter().map(|p| p.y_mm).sum();
+            let pin_count = Decimal::new(component.pins.len().try_into()?, 0);
+            component.origin_x_mm = total_x / pin_count;
+            component.origin_y_mm = total_y / pin_count;
+        }
+
+        let testvias = content
+            .testvias
+            .iter()
+            .map(|tv| BoardTestVia {
+                name: tv.testvia.clone(),
+                net_name: tv.net_name.clone(),
+                refdes: tv.refdes.clone(),
+                pin_number: tv.pin_number.clone(),
+                x_mm: to_mm(&content.units, tv.via_x),
+                y_mm: to_mm(&content.units, tv.via_y),
+                radius_mm: to_mm(&content.units, tv.radius),
+            })
+            .collect();
+
+        let vias = content
+            .vias
+            .iter()
+            .map(|via| BoardVia {
+                id: via.via_id.clone(),
+                net_name: via.net_name.clone(),
+                x_mm: to_mm(&content.units, via.via_x),
+                y_mm: to_mm(&content.units, via.via_y),
+                radius_mm: via.radius.map(|r| to_mm(&content.units, r)),
+                start_layer: via.start_layer.clone(),
+                end_layer: via.end_layer.clone(),
+            })
+            .collect();
+
+        let symbol_graphics = content.graphic_data.iter().filter_map(|g| {
+            Some(BoardGraphic {
+                class: String::new(),
+                subclass: g.subclass.clone(),
+                number: g.graphic_data_number,
+                record_tag: g.record_tag.clone(),
+                refdes: g.refdes.clone(),
+                sym_name: g.sym_name.clone(),
+                net_name: String::new(),
+                primitive: Primitive::from_fields(
+                    &content.units,
+                    &g.graphic_data_name,
+                    &g.graphic_data,
+                )?,
+            })
+        });
+        let classed_graphics = content.classed_graphic_data.iter().filter_map(|g| {
+            Some(BoardGraphic {
+                class: g.class.clone(),
+                subclass: g.subclass.clone(),
+                number: g.graphic_data_number,
+                record_tag: g.record_tag.clone(),
+                refdes: String::new(),
+                sym_name: String::new(),
+                net_name: g.net_name.clone(),
+                primitive: Primitive::from_fields(
+                    &content.units,
+                    &g.graphic_data_name,
+                    &g.graphic_data,
+                )?,
+            })
+        });
+        let graphics: Vec<BoardGraphic> = symbol_graphics.chain(classed_graphics).collect();
+
+        let pads = PadIndex::new(&graphics);
+        infer_pad_shapes(&mut components, &pads);
+        infer_pad_sizes(&mut components);
+        infer_pad_types(&mut components, &pads);
+        let symbol_outlines = symbol_outlines(&components, &graphics);
+        for component in components.values_mut() {
+            let symbol_outline = symbol_outlines
+                .get(&component.sym_name)
+                .map_or(&[][..], Vec::as_slice);
+            compute_bounds(component, &graphics, symbol_outline);
+        }
+        let texts = TextItem::from_graphics(&content.units, &graphics, &components);
+
+        Ok(Self {
+            components,
+            testvias,
+            vias,
+            graphics,
+            texts,
+        })
+    }
+
+    /// Groups the graphics that pass `filter` into figures, i.e. the records
+    /// that share a class, layer, net, and graphic data number. Figures are
+    /// returned in the order they first appear.
+    pub(crate) fn figures(
+        &self,
+        filter: impl Fn(&BoardGraphic) -> bool,
+    ) -> Vec<Vec<&BoardGraphic>> {
+        let mut figures: Vec<Vec<&BoardGraphic>> = Vec::new();
+        let mut index: BTreeMap<(&str, &str, &str, u64), usize> = BTreeMap::new();
+        for graphic in self.graphics.iter().filter(|g| filter(g)) {
+            let key = (
+                graphic.class.as_str(),
+                graphic.subclass.as_str(),
+                graphic.net_name.as_str(),
+                graphic.number,
+            );
+            let i = *index.entry(key).or_insert_with(|| {
+                figures.push(Vec::new());
+                figures.len() - 1
+            });
+            figures[i].push(graphic);
+        }
+        figures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{board_from, board_with_description};
+
+    #[test]
+    fn test_from_parsed() {
+        let content = b"A!UNIT!mils!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!R1!1!R0402!NO!90!\n\
+S!C1!2!C0402!YES!180!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!VCC!R1!1!1!1000!2000!!10!\n\
+S!GND!R1!2!2!1100!2000!!10!\n\
+S!GND!C1!1!1!3000!4000!!10!\n\
+A!VIAID!NET_NAME!VIA_X!VIA_Y!RADIUS!\n\
+S!1!GND!500!500!8!\n\
+S!2!GND!600!500!!\n";
+        let description = b"B|1|B|1|PN\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\n\
+01\tCAP 100NF 16V X7R 0402\t1\tC1\t\n";
+        let board = board_with_description(content, description);
+
+        let r1 = &board.components["R1"];
+        assert_eq!(r1.sym_name, "R0402");
+        assert_eq!((r1.insertion_code, r1.rotation), (Some(1), 90));
+        assert_eq!((r1.mirrored, r1.side), (false, Side::Top));
+        assert_eq!(r1.origin_x_mm, Decimal::new(26670, 3));
+        assert_eq!(r1.origin_y_mm, Decimal::new(50800, 3));
+        assert_eq!(r1.pins[0].net_name, "VCC");
+        assert_eq!(r1.pins[0].radius_mm, Decimal::new(2540, 4));
+        assert_eq!(r1.bom_index, None);
+
+        let c1 = &board.components["C1"];
+        assert_eq!((c1.mirrored, c1.side), (true, Side::Bottom));
+        assert_eq!(c1.bom_index, Some(0));
+        assert_eq!(c1.description, "CAP 100NF 16V X7R 0402");
+        assert_eq!(c1.kind, ComponentKind::Capacitor);
+
+        assert_eq!(board.vias.len(), 2);
+        assert_eq!(board.vias[0].x_mm, Decimal::new(12700, 3));
+        assert_eq!(board.vias[0].radius_mm, Some(Decimal::new(2032, 4)));
+        assert_eq!(board.vias[1].radius_mm, None);
+    }
+
+    #[test]
+    fn test_component_bounds() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!U1!1!SOIC!NO!90!\n\
+S!U2!1!SOIC!NO!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!U1!1!1!10!9!!0.5!\n\
+S!B!U1!2!2!10!11!!0.5!\n\
+S!A!U2!1!1!30!9!!0.5!\n\
+S!B!U2!2!2!30!11!!0.5!\n\
+A!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!GRAPHIC_DATA_2!\
+GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!GRAPHIC_DATA_8!\
+GRAPHIC_DATA_9!SUBCLASS!SYM_NAME!REFDES!\n\
+S!RECTANGLE!1!1 1 0!7!8!13!12!0!!!!!ASSEMBLY_TOP!SOIC!U1!\n\
+S!LINE!2!2 1 0!0!0!50!50!0.1!!!!!SILKSCREEN_TOP!SOIC!U1!\n\
+S!RECTANGLE!3!3 1 0!8!9.5!12!10.5!0!!!!!ASSEMBLY_TOP!SOIC!!\n\
+S!RECTANGLE!4!4 1 0!40!40!50!50!0!!!!!ASSEMBLY_TOP!SOT23!!\n";
+        let mut board = board_from(content);
+        assert_eq!(board.graphics.len(), 4);
+
+        let component = &board.components["U1"];
+        let bounds = component.bounds.unwrap();
+        assert_eq!((bounds.min_x_mm, bounds.max_x_mm), (7.0, 13.0));
+        assert_eq!((bounds.min_y_mm, bounds.max_y_mm), (8.0, 12.0));
+
+        // Rotated by 90 degrees, so the footprint is taller than it is wide
+        let local = component.footprint_bounds.unwrap();
+        assert!((local.width_mm() - 4.0).abs() < 1e-9);
+        assert!((local.height_mm() - 6.0).abs() < 1e-9);
+        let oriented = component.oriented_bounds.unwrap();
+        assert!((oriented.center_x_mm - 10.0).abs() < 1e-9);
+        assert!((oriented.center_y_mm - 10.0).abs() < 1e-9);
+        assert_eq!(oriented.rotation, 90.0);
+        let courtyard = component.courtyard.unwrap();
+        assert!((courtyard.width_mm - 4.5).abs() < 1e-9);
+        assert!((courtyard.height_mm - 6.5).abs() < 1e-9);
+
+        // The symbol's shared outline is drawn around U1, so it's rotated
+        // onto U2. The SOT23 outline surrounds no component, so it's skipped.
+        let bounds = board.components["U2"].bounds.unwrap();
+        assert!((bounds.min_x_mm - 29.5).abs() < 1e-9);
+        assert!((bounds.max_x_mm - 30.5).abs() < 1e-9);
+        assert!((bounds.min_y_mm - 8.0).abs() < 1e-9);
+        assert!((bounds.max_y_mm - 12.0).abs() < 1e-9);
+
+        // Wide pads stick out of the outline
+        let component = board.components.get_mut("U1").unwrap();
+        for pin in &mut component.pins {
+            pin.shape = PadShape::Rect;
+            pin.width_mm = Decimal::new(8, 0);
+        }
+        compute_bounds(component, &[], &[]);
+        let bounds = component.bounds.unwrap();
+        assert_eq!((bounds.min_x_mm, bounds.max_x_mm), (6.0, 14.0));
+        assert_eq!((bounds.min_y_mm, bounds.max_y_mm), (8.5, 11.5));
+    }
+}
@@ -0,0 +1,432 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/classify.rs - Component classification for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use rust_decimal::Decimal;
+
+use super::netinfo::is_word_separator;
+use super::netinfo::words;
+
+/// The kind of a component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComponentKind {
+    /// A resistor or resistor network.
+    Resistor,
+    /// A zero-ohm resistor or jumper.
+    Jumper,
+    /// A capacitor.
+    Capacitor,
+    /// An inductor or choke.
+    Inductor,
+    /// A ferrite bead.
+    FerriteBead,
+    /// A diode, including LEDs and TVS diodes.
+    Diode,
+    /// A transistor, including MOSFETs.
+    Transistor,
+    /// An integrated circuit.
+    Ic,
+    /// A connector, header, or socket.
+    Connector,
+    /// A crystal, resonator, or oscillator.
+    Crystal,
+    /// A fuse.
+    Fuse,
+    /// Anything else.
+    Other,
+}
+
+/// The value and ratings of a passive component, parsed from its BOM
+/// description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PassiveValue {
+    /// The value in ohms, farads, or henries, depending on the kind of
+    /// component. Ferrite beads are rated by impedance, in ohms.
+    pub value: Option<Decimal>,
+    /// The tolerance in percent.
+    pub tolerance: Option<Decimal>,
+    /// The voltage rating in volts.
+    pub voltage: Option<Decimal>,
+    /// The imperial package size code (e.g., "0402").
+    pub package: Option<String>,
+}
+
+const PACKAGES: &[&str] = &[
+    "01005", "0201", "0402", "0603", "0805", "1206", "1210", "1812", "2010", "2512",
+];
+
+/// The words in a BOM description that name a kind of component, in order of
+/// precedence when none of them starts the description.
+const TYPE_WORDS: &[(ComponentKind, &[&str])] = &[
+    (ComponentKind::Jumper, &["JUMPER", "JUMP", "JMP"]),
+    (
+        ComponentKind::Crystal,
+        &["XTAL", "CRYSTAL", "RESONATOR", "OSC", "OSCILLATOR"],
+    ),
+    (ComponentKind::Resistor, &["RES", "RESISTOR", "RESNET"]),
+    (ComponentKind::Capacitor, &["CAP", "CAPACITOR", "MLCC"]),
+    (ComponentKind::FerriteBead, &["BEAD", "FERRITE"]),
+    (
+        ComponentKind::Inductor,
+        &["IND", "INDUCTOR", "CHOKE", "COIL"],
+    ),
+    (ComponentKind::Fuse, &["FUSE", "POLYFUSE", "POLYSWITCH"]),
+    (
+        ComponentKind::Diode,
+        &["DIODE", "LED", "SCHOTTKY", "ZENER", "TVS"],
+    ),
+    (
+        ComponentKind::Transistor,
+        &["MOSFET", "FET", "TRANS", "TRANSISTOR", "NPN", "PNP"],
+    ),
+    (
+        ComponentKind::Connector,
+        &["CONN", "CONNECTOR", "HEADER", "SOCKET", "JACK"],
+    ),
+    (ComponentKind::Ic, &["IC"]),
+];
+
+/// Returns the kind of component a type word names, if any.
+fn type_word_kind(word: &str) -> Option<ComponentKind> {
+    TYPE_WORDS
+        .iter()
+        .find(|(_, type_words)| type_words.contains(&word))
+        .map(|(kind, _)| *kind)
+}
+
+/// Returns whether a character separates the words of a BOM description.
+/// Only whitespace and list punctuation do, so that values like "4.7UF" and
+/// "+-5%" are kept whole.
+fn is_description_separator(c: char) -> bool {
+    c.is_whitespace() || c == ',' || c == ';'
+}
+
+/// Returns the reference designator without its number (e.g., "PR" for
+/// "PR123"), in uppercase.
+pub(crate) fn refdes_prefix(refdes: &str) -> String {
+    refdes
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Parses a package size code, optionally prefixed by a letter (e.g.,
+/// "R0402").
+fn parse_package(word: &str) -> Option<&'static str> {
+    let upper = word.to_ascii_uppercase();
+    let code = match upper.strip_prefix(|c: char| c.is_ascii_alphabetic()) {
+        Some(code) => code,
+        None => &upper,
+    };
+    PACKAGES.iter().find(|p| **p == code).copied()
+}
+
+/// Parses a quantity such as "10K", "4K7", "22UF", or "0.1uF".
+///
+/// # Arguments
+///
+/// * `word` - The word to parse.
+/// * `units` - The unit suffixes the word may end with.
+/// * `multiplier` - Maps a multiplier letter to its power of ten.
+/// * `bare` - Whether a number with no unit or multiplier is accepted.
+fn parse_quantity(
+    word: &str,
+    units: &[&str],
+    multiplier: fn(char) -> Option<i32>,
+    bare: bool,
+) -> Option<Decimal> {
+    let upper = word.to_ascii_uppercase();
+    let mut body = word;
+    let mut has_unit = false;
+    for unit in units {
+        if upper.ends_with(unit) && upper.len() > unit.len() {
+            body = &word[..word.len() - unit.len()];
+            has_unit = true;
+            break;
+        }
+    }
+
+    let split = body
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(body.len());
+    if split == 0 {
+        return None;
+    }
+    let (number, rest) = body.split_at(split);
+    let mut rest = rest.chars();
+    let exponent = match rest.next() {
+        Some(c) => multiplier(c)?,
+        None if has_unit || bare => 0,
+        None => return None,
+    };
+    let fraction: String = rest.collect();
+    if !fraction.chars().all(|c| c.is_ascii_digit()) || !fraction.is_empty() && number.contains('.')
+    {
+        return None;
+    }
+
+    let text = if fraction.is_empty() {
+        number.to_string()
+    } else {
+        format!("{}.{}", number, fraction)
+    };
+    let value: Decimal = text.parse().ok()?;
+    let scale = if exponent >= 0 {
+        Decimal::from(10u64.pow(exponent as u32))
+    } else {
+        Decimal::new(1, (-exponent) as u32)
+    };
+    Some(value.checked_mul(scale)?.normalize())
+}
+
+fn ohms_multiplier(c: char) -> Option<i32> {
+    match c {
+        'R' | 'r' => Some(0),
+        'K' | 'k' => Some(3),
+        'M' => Some(6),
+        'm' => Some(-3),
+        _ => None,
+    }
+}
+
+fn farads_multiplier(c: char) -> Option<i32> {
+    match c {
+        'P' | 'p' => Some(-12),
+        'N' | 'n' => Some(-9),
+        'U' | 'u' | 'µ' => Some(-6),
+        'M' | 'm' => Some(-3),
+        _ => None,
+    }
+}
+
+fn henries_multiplier(c: char) -> Option<i32> {
+    match c {
+        'N' | 'n' => Some(-9),
+        'U' | 'u' | 'µ' => Some(-6),
+        'M' | 'm' => Some(-3),
+        _ => None,
+    }
+}
+
+impl ComponentKind {
+    /// Classifies a component from its BOM description, or from its
+    /// reference designator prefix if the description doesn't say.
+    ///
+    /// The description's type words (e.g., "RES", "LED", or "IC") are
+    /// matched whole. A type word at the start of the description decides
+    /// the kind, so "IC RESET SUPERVISOR" is an IC and "LED GREEN INDICATOR"
+    /// is a diode. Otherwise, a type word anywhere in the description is
+    /// used, checking jumpers and crystals before resistors and ICs last.
+    ///
+    /// Reference designator prefixes include the `P`-prefixed variants used
+    /// in ASUS power sections (e.g., "PR", "PC", "PL", "PU", and "PQ").
+    ///
+    /// # Arguments
+    ///
+    /// * `refdes` - The reference designator of the component.
+    /// * `description` - The BOM description of the component, or an empty
+    ///   string if it has none.
+    pub fn classify(refdes: &str, description: &str) -> Self {
+        let upper = description.to_ascii_uppercase();
+        let value_words: Vec<&str> = words(&upper, is_description_separator).collect();
+        let tokens: Vec<&str> = words(&upper, is_word_separator).collect();
+
+        let leading = tokens.first().and_then(|t| type_word_kind(t));
+        let kind = leading.or_else(|| {
+            TYPE_WORDS
+                .iter()
+                .find(|(_, type_words)| tokens.iter().any(|t| type_words.contains(t)))
+                .map(|(kind, _)| *kind)
+        });
+        match kind {
+            Some(Self::Resistor) => {
+                let zero = value_words
+                    .iter()
+                    .any(|w| matches!(*w, "0" | "0R" | "0OHM" | "0OHMS"));
+                return if zero { Self::Jumper } else { Self::Resistor };
+            }
+            Some(kind) => return kind,
+            None => (),
+        }
+
+        match refdes_prefix(refdes).as_str() {
+            "R" | "PR" | "RN" | "RP" => Self::Resistor,
+            "JP" | "PJ" | "PJP" => Self::Jumper,
+            "C" | "PC" | "CE" | "EC" => Self::Capacitor,
+            "L" | "PL" => Self::Inductor,
+            "FB" | "PFB" | "BD" => Self::FerriteBead,
+            "D" | "PD" | "ZD" | "LED" => Self::Diode,
+            "Q" | "PQ" => Self::Transistor,
+            "U" | "PU" | "IC" => Self::Ic,
+            "J" | "CN" | "CON" | "JK" => Self::Connector,
+            "Y" | "X" | "PY" | "XTAL" => Self::Crystal,
+            "F" | "PF" | "FU" => Self::Fuse,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns whether a component of this kind passes DC between its two
+    /// terminals.
+    pub fn is_series(&self) -> bool {
+        matches!(
+            self,
+            Self::Resistor | Self::Jumper | Self::Inductor | Self::FerriteBead | Self::Fuse
+        )
+    }
+}
+
+impl PassiveValue {
+    /// Parses the value and ratings of a passive component from its BOM
+    /// description (e.g., "RES 10K 1% 0402" or "CAP 22UF 6.3V X5R 0603").
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the component.
+    /// * `description` - The BOM description of the component.
+    ///
+    /// # Returns
+    ///
+    /// The parsed value, or `None` if the component isn't a resistor,
+    /// jumper, capacitor, inductor, or ferrite bead.
+    pub fn parse(kind: ComponentKind, description: &str) -> Option<Self> {
+        let mut passive = Self {
+            value: None,
+            tolerance: None,
+            voltage: None,
+            package: None,
+        };
+
+        for word in words(description, is_description_separator).skip(1) {
+            if passive.package.is_none()
+                && let Some(package) = parse_package(word)
+            {
+                passive.package = Some(package.to_string());
+                continue;
+            }
+            if let Some(percent) = word.strip_suffix('%') {
+                let percent = percent.trim_start_matches(['+', '-', '/', '±']);
+                passive.tolerance = passive.tolerance.or(percent.parse().ok());
+                continue;
+            }
+            if kind == ComponentKind::Capacitor
+                && passive.voltage.is_none()
+                && let Some(voltage) =
+                    parse_quantity(word, &["V"], |c| (c == 'V').then_some(0), false)
+            {
+                passive.voltage = Some(voltage);
+                continue;
+            }
+            if passive.value.is_some() {
+                continue;
+            }
+            passive.value = match kind {
+                ComponentKind::Resistor | ComponentKind::Jumper | ComponentKind::FerriteBead => {
+                    parse_quantity(word, &["OHMS", "OHM", "Ω"], ohms_multiplier, true)
+                }
+                ComponentKind::Capacitor => parse_quantity(word, &["F"], farads_multiplier, false),
+                ComponentKind::Inductor => parse_quantity(word, &["H"], henries_multiplier, false),
+                _ => return None,
+            };
+        }
+
+        if kind == ComponentKind::Jumper && passive.value.is_none() {
+            passive.value = Some(Decimal::ZERO);
+        }
+
+        Some(passive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            ComponentKind::classify("R1", "RES 10K 1% 0402"),
+            ComponentKind::Resistor
+        );
+        assert_eq!(
+            ComponentKind::classify("R2", "RES 0 5% 0402"),
+            ComponentKind::Jumper
+        );
+        assert_eq!(
+            ComponentKind::classify("X1", "RESONATOR 24MHZ"),
+            ComponentKind::Crystal
+        );
+        assert_eq!(
+            ComponentKind::classify("PQ12", ""),
+            ComponentKind::Transistor
+        );
+        assert_eq!(ComponentKind::classify("PC3", ""), ComponentKind::Capacitor);
+        assert_eq!(ComponentKind::classify("TP1", ""), ComponentKind::Other);
+    }
+
+    #[test]
+    fn test_classify_whole_words() {
+        let classify = |description| ComponentKind::classify("U1", description);
+        assert_eq!(classify("IC RESET SUPERVISOR"), ComponentKind::Ic);
+        assert_eq!(classify("IC USB TRANSCEIVER"), ComponentKind::Ic);
+        assert_eq!(classify("IC MOSFET DRIVER"), ComponentKind::Ic);
+        assert_eq!(classify("LED GREEN INDICATOR"), ComponentKind::Diode);
+        assert_eq!(classify("CONN USB-C RES"), ComponentKind::Connector);
+        assert_eq!(classify("CHIP RES 10K 0402"), ComponentKind::Resistor);
+        assert_eq!(classify("SMD CAP-CER 1UF"), ComponentKind::Capacitor);
+        assert_eq!(
+            ComponentKind::classify("DS1", "INDICATOR LAMP"),
+            ComponentKind::Other
+        );
+    }
+
+    #[test]
+    fn test_passive_value() {
+        let res = PassiveValue::parse(ComponentKind::Resistor, "RES 10K 1% 0402").unwrap();
+        assert_eq!(res.value, Some(Decimal::new(10000, 0)));
+        assert_eq!(res.tolerance, Some(Decimal::ONE));
+        assert_eq!(res.package.as_deref(), Some("0402"));
+
+        let res = PassiveValue::parse(ComponentKind::Resistor, "RES 4K7 +-5% R0603").unwrap();
+        assert_eq!(res.value, Some(Decimal::new(4700, 0)));
+        assert_eq!(res.tolerance, Some(Decimal::new(5, 0)));
+        assert_eq!(res.package.as_deref(), Some("0603"));
+
+        let cap = PassiveValue::parse(ComponentKind::Capacitor, "CAP 22UF 6.3V X5R 0603").unwrap();
+        assert_eq!(cap.value, Some(Decimal::new(22, 6)));
+        assert_eq!(cap.voltage, Some(Decimal::new(63, 1)));
+        assert_eq!(cap.package.as_deref(), Some("0603"));
+
+        let ind = PassiveValue::parse(ComponentKind::Inductor, "IND 2.2UH 20%").unwrap();
+        assert_eq!(ind.value, Some(Decimal::new(22, 7)));
+
+        assert_eq!(PassiveValue::parse(ComponentKind::Ic, "IC PWM"), None);
+
+        // Values too large for a decimal are skipped rather than overflowing
+        let res = PassiveValue::parse(
+            ComponentKind::Resistor,
+            "RES 79228162514264337593543950335K 1%",
+        )
+        .unwrap();
+        assert_eq!(res.value, None);
+        assert_eq!(res.tolerance, Some(Decimal::ONE));
+    }
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/connectivity.rs - Net connectivity through series components.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
+use super::Board;
+use super::ComponentKind;
+use super::NetInfo;
+use super::Netlist;
+
+/// A two-terminal component that connects two nets in series.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeriesElement {
+    /// The reference designator of the component.
+    pub refdes: String,
+    /// The kind of component.
+    pub kind: ComponentKind,
+    /// The names of the two nets the component connects.
+    pub nets: [String; 2],
+}
+
+/// One step along a path between nets.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathStep {
+    /// The reference designator of the series component passed through.
+    pub refdes: String,
+    /// The net the step starts from.
+    pub from_net: String,
+    /// The net the step ends at.
+    pub to_net: String,
+}
+
+/// A net reached from another net through series components.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelatedNet {
+    /// The name of the net.
+    pub net: String,
+    /// The shortest path of series components to the net.
+    pub path: Vec<PathStep>,
+}
+
+/// A graph with nets as nodes and series components as edges.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectivityGraph {
+    /// The series components on the board.
+    pub elements: Vec<SeriesElement>,
+    /// The indices of the elements in [ConnectivityGraph::elements]
+    /// connected to each net, by net name.
+    pub edges: BTreeMap<String, Vec<usize>>,
+}
+
+impl ConnectivityGraph {
+    /// Builds the series connectivity graph of a board.
+    ///
+    /// Components count as series elements if they have exactly two pins on
+    /// two different nets and are [series](ComponentKind::is_series)
+    /// components. No-connect nets are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    /// * `netlist` - The board's netlist.
+    pub fn new(board: &Board, netlist: &Netlist) -> Self {
+        let mut elements = Vec::new();
+        let mut edges: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+
+        for (refdes, component) in &board.components {
+            let [a, b] = component.pins.as_slice() else {
+                continue;
+            };
+            if a.net_name == b.net_name
+                || [a, b]
+                    .iter()
+                    .any(|p| netlist.net(&p.net_name).is_none_or(|net| net.no_connect))
+            {
+                continue;
+            }
+            if !component.kind.is_series() {
+                continue;
+            }
+
+            let index = elements.len();
+            for net in [&a.net_name, &b.net_name] {
+                edges.entry(net.clone()).or_default().push(index);
+            }
+            elements.push(SeriesElement {
+                refdes: refdes.clone(),
+                kind: component.kind,
+                nets: [a.net_name.clone(), b.net_name.clone()],
+            });
+        }
+
+        Self { elements, edges }
+    }
+
+    /// Finds the nets connected to a net through at most `max_depth` series
+    /// components, along with the shortest path to each.
+    ///
+    /// Ground nets can be reached but aren't traced through, since they would
+    /// otherwise connect nearly every net on the board through pull-down
+    /// resistors. The results are sorted by path length and then by net name.
+    ///
+    /// # Arguments
+    ///
+    /// * `net` - The name of the net to start from.
+    /// * `max_depth` - The maximum number of series components to pass
+    ///   through.
+    pub fn related_nets(&self, net: &str, max_depth: usize) -> Vec<RelatedNet> {
+        let mut paths: BTreeMap<&str, Vec<PathStep>> = BTreeMap::new();
+        paths.insert(net, Vec::new());
+
+        let mut queue = VecDeque::from([net]);
+        while let Some(current) = queue.pop_front() {
+            let path = paths[current].clone();
+            if path.len() >= max_depth || (!path.is_empty() && NetInfo::from_name(current).ground) {
+                continue;
+            }
+
+            for &index in self.edges.get(current).into_iter().flatten() {
+                let element = &self.elements[index];
+                let next = if element.nets[0] == current {
+                    &element.nets[1]
+                } else {
+                    &element.nets[0]
+                };
+                if paths.contains_key(next.as_str()) {
+                    continue;
+                }
+
+                let mut next_path = path.clone();
+                next_path.push(PathStep {
+                    refdes: element.refdes.clone(),
+                    from_net: current.to_string(),
+                    to_net: next.clone(),
+                });
+                paths.insert(next, next_path);
+                queue.push_back(next);
+            }
+        }
+
+        let mut related: Vec<RelatedNet> = paths
+            .into_iter()
+            .filter(|(name, _)| *name != net)
+            .map(|(name, path)| RelatedNet {
+                net: name.to_string(),
+                path,
+            })
+            .collect();
+        related.sort_by(|a, b| a.path.len().cmp(&b.path.len()).then(a.net.cmp(&b.net)));
+        related
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_with_description;
+
+    #[test]
+    fn test_related_nets() {
+        let content = b"A!UNIT!millimeters!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!+5V!PL1!1!1!0!0!!0.25!\n\
+S!+5V_F!PL1!2!2!1!0!!0.25!\n\
+S!+5V_F!R1!1!1!0!2!!0.25!\n\
+S!EN!R1!2!2!1!2!!0.25!\n\
+S!EN!R2!1!1!0!4!!0.25!\n\
+S!GND!R2!2!2!1!4!!0.25!\n\
+S!GND!R3!1!1!0!6!!0.25!\n\
+S!OTHER!R3!2!2!1!6!!0.25!\n\
+S!+5V_F!C1!1!1!0!8!!0.25!\n\
+S!GND!C1!2!2!1!8!!0.25!\n";
+        let description = b"BOARD|1.00|BOARD|1.00|PN\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\n\
+1\tCHOKE 1UH 3A\t1\tPL1\t\n\
+2\tCAP 10U 10V 0603\t1\tC1\t\n";
+        let board = board_with_description(content, description);
+        let graph = ConnectivityGraph::new(&board, &Netlist::from_board(&board));
+
+        assert_eq!(graph.elements.len(), 4);
+        assert_eq!(graph.elements[0].kind, ComponentKind::Inductor);
+
+        let related = graph.related_nets("+5V", 3);
+        let names: Vec<_> = related.iter().map(|r| r.net.as_str()).collect();
+        assert_eq!(names, ["+5V_F", "EN", "GND"]);
+        let refdes: Vec<_> = related[2].path.iter().map(|s| s.refdes.as_str()).collect();
+        assert_eq!(refdes, ["PL1", "R1", "R2"]);
+
+        assert_eq!(graph.related_nets("+5V", 1).len(), 1);
+    }
+}
@@ -0,0 +1,892 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/graphics.rs - Graphic primitive decoding for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::HashMap;
+use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::TAU;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::parser::Units;
+
+use super::board::to_mm;
+
+/// An axis-aligned rectangle, in millimeters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    /// The smallest X-coordinate.
+    pub min_x_mm: f64,
+    /// The smallest Y-coordinate.
+    pub min_y_mm: f64,
+    /// The largest X-coordinate.
+    pub max_x_mm: f64,
+    /// The largest Y-coordinate.
+    pub max_y_mm: f64,
+}
+
+impl Rect {
+    /// Returns the smallest rectangle containing a set of discs, given as
+    /// `(x, y, radius)` tuples, or `None` if there are none.
+    pub fn from_discs(discs: impl IntoIterator<Item = (f64, f64, f64)>) -> Option<Self> {
+        discs.into_iter().fold(None, |rect, (x, y, r)| {
+            let disc = Self {
+                min_x_mm: x - r,
+                min_y_mm: y - r,
+                max_x_mm: x + r,
+                max_y_mm: y + r,
+            };
+            Some(match rect {
+                Some(rect) => rect.union(&disc),
+                None => disc,
+            })
+        })
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min_x_mm: self.min_x_mm.min(other.min_x_mm),
+            min_y_mm: self.min_y_mm.min(other.min_y_mm),
+            max_x_mm: self.max_x_mm.max(other.max_x_mm),
+            max_y_mm: self.max_y_mm.max(other.max_y_mm),
+        }
+    }
+
+    /// Returns the rectangle grown by `margin_mm` on every side.
+    pub fn expanded(&self, margin_mm: f64) -> Self {
+        Self {
+            min_x_mm: self.min_x_mm - margin_mm,
+            min_y_mm: self.min_y_mm - margin_mm,
+            max_x_mm: self.max_x_mm + margin_mm,
+            max_y_mm: self.max_y_mm + margin_mm,
+        }
+    }
+
+    /// The width of the rectangle.
+    pub fn width_mm(&self) -> f64 {
+        self.max_x_mm - self.min_x_mm
+    }
+
+    /// The height of the rectangle.
+    pub fn height_mm(&self) -> f64 {
+        self.max_y_mm - self.min_y_mm
+    }
+
+    /// The center of the rectangle.
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.min_x_mm + self.max_x_mm) / 2.0,
+            (self.min_y_mm + self.max_y_mm) / 2.0,
+        )
+    }
+
+    /// The area of the rectangle, in square millimeters.
+    pub fn area_mm2(&self) -> f64 {
+        self.width_mm() * self.height_mm()
+    }
+
+    /// Returns whether a point is inside or on the edge of the rectangle.
+    pub fn contains(&self, x_mm: f64, y_mm: f64) -> bool {
+        (self.min_x_mm..=self.max_x_mm).contains(&x_mm)
+            && (self.min_y_mm..=self.max_y_mm).contains(&y_mm)
+    }
+}
+
+/// A shape decoded from the `GRAPHIC_DATA_*` fields of a graphic data record,
+/// in board coordinates and millimeters.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Primitive {
+    /// A straight line segment.
+    Line {
+        x1_mm: f64,
+        y1_mm: f64,
+        x2_mm: f64,
+        y2_mm: f64,
+        width_mm: f64,
+    },
+    /// A circular arc from (`x1_mm`, `y1_mm`) to (`x2_mm`, `y2_mm`) around
+    /// (`xc_mm`, `yc_mm`). An arc whose ends coincide is a full circle.
+    Arc {
+        x1_mm: f64,
+        y1_mm: f64,
+        x2_mm: f64,
+        y2_mm: f64,
+        xc_mm: f64,
+        yc_mm: f64,
+        radius_mm: f64,
+        width_mm: f64,
+        clockwise: bool,
+    },
+    /// An axis-aligned rectangle with corners (`x1_mm`, `y1_mm`) and
+    /// (`x2_mm`, `y2_mm`).
+    Rectangle {
+        x1_mm: f64,
+        y1_mm: f64,
+        x2_mm: f64,
+        y2_mm: f64,
+        filled: bool,
+    },
+    /// A filled circle centered on (`x_mm`, `y_mm`).
+    Circle {
+        x_mm: f64,
+        y_mm: f64,
+        diameter_mm: f64,
+    },
+    /// A filled square centered on (`x_mm`, `y_mm`).
+    Square { x_mm: f64, y_mm: f64, size_mm: f64 },
+    /// A filled obround centered on (`x_mm`, `y_mm`), rounded along its
+    /// shorter sides.
+    Oblong {
+        x_mm: f64,
+        y_mm: f64,
+        width_mm: f64,
+        height_mm: f64,
+    },
+    /// A text string anchored at (`x_mm`, `y_mm`).
+    Text {
+        x_mm: f64,
+        y_mm: f64,
+        /// The counter-clockwise rotation in degrees.
+        rotation: f64,
+        mirrored: bool,
+        /// The justification (e.g., "LEFT", "CENTER", or "RIGHT").
+        justification: String,
+        /// The raw font parameters.
+        font: String,
+        text: String,
+    },
+}
+
+/// Parses a numeric graphic data field, accepting either decimal separator.
+fn number(field: &str) -> Option<f64> {
+    Decimal::from_str(&field.trim().replace(',', "."))
+        .ok()?
+        .to_f64()
+}
+
+impl Primitive {
+    /// Decodes a primitive from a graphic data record.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units of the file the record is from.
+    /// * `name` - The record's `GRAPHIC_DATA_NAME` (e.g., "LINE" or "ARC").
+    /// * `fields` - The record's `GRAPHIC_DATA_1` through `GRAPHIC_DATA_9`.
+    ///
+    /// # Returns
+    ///
+    /// The primitive, or `None` if the record is of an unknown kind or its
+    /// fields are malformed.
+    pub fn from_fields(units: &Units, name: &str, fields: &[String; 9]) -> Option<Self> {
+        let mm = |index: usize| -> Option<f64> {
+            let value = Decimal::from_str(&fields[index].trim().replace(',', ".")).ok()?;
+            to_mm(units, value).to_f64()
+        };
+        // Widths are often left empty
+        let width = |index: usize| mm(index).unwrap_or(0.0);
+
+        let primitive = match name.trim().to_ascii_uppercase().as_str() {
+            "LINE" => Self::Line {
+                x1_mm: mm(0)?,
+                y1_mm: mm(1)?,
+                x2_mm: mm(2)?,
+                y2_mm: mm(3)?,
+                width_mm: width(4),
+            },
+            "ARC" => Self::Arc {
+                x1_mm: mm(0)?,
+                y1_mm: mm(1)?,
+                x2_mm: mm(2)?,
+                y2_mm: mm(3)?,
+                xc_mm: mm(4)?,
+                yc_mm: mm(5)?,
+                radius_mm: mm(6)?,
+                width_mm: width(7),
+                clockwise: fields[8].trim().eq_ignore_ascii_case("CLOCKWISE"),
+            },
+            "RECTANGLE" => Self::Rectangle {
+                x1_mm: mm(0)?,
+                y1_mm: mm(1)?,
+                x2_mm: mm(2)?,
+                y2_mm: mm(3)?,
+                filled: fields[4].trim() == "1",
+            },
+            "CIRCLE" => Self::Circle {
+                x_mm: mm(0)?,
+                y_mm: mm(1)?,
+                diameter_mm: mm(2)?,
+            },
+            "SQUARE" => Self::Square {
+                x_mm: mm(0)?,
+                y_mm: mm(1)?,
+                size_mm: mm(2)?,
+            },
+            "OBLONG_X" | "OBLONG_Y" => Self::Oblong {
+                x_mm: mm(0)?,
+                y_mm: mm(1)?,
+                width_mm: mm(2)?,
+                height_mm: mm(3)?,
+            },
+            "TEXT" => Self::Text {
+                x_mm: mm(0)?,
+                y_mm: mm(1)?,
+                rotation: number(&fields[2]).unwrap_or(0.0),
+                mirrored: fields[3].trim().eq_ignore_ascii_case("YES"),
+                justification: fields[4].trim().to_string(),
+                font: fields[5].trim().to_string(),
+                text: fields[6].clone(),
+            },
+            _ => return None,
+        };
+        Some(primitive)
+    }
+
+    /// Returns the start and end angles of an arc in radians, with the end
+    /// angle counter-clockwise from the start angle.
+    pub(crate) fn arc_angles(
+        (x1, y1): (f64, f64),
+        (x2, y2): (f64, f64),
+        (xc, yc): (f64, f64),
+        clockwise: bool,
+    ) -> (f64, f64) {
+        let a1 = (y1 - yc).atan2(x1 - xc);
+        let a2 = (y2 - yc).atan2(x2 - xc);
+        let (start, end) = if clockwise { (a2, a1) } else { (a1, a2) };
+        let mut sweep = (end - start).rem_euclid(TAU);
+        if sweep < 1e-9 {
+            sweep = TAU;
+        }
+        (start, start + sweep)
+    }
+
+    /// Returns discs, as `(x, y, radius)` tuples, whose union covers the
+    /// extent of the primitive. Text has no known extent, so it has none.
+    pub(crate) fn extent_discs(&self) -> Vec<(f64, f64, f64)> {
+        match *self {
+            Self::Line {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                width_mm,
+            } => vec![
+                (x1_mm, y1_mm, width_mm / 2.0),
+                (x2_mm, y2_mm, width_mm / 2.0),
+            ],
+            Self::Arc {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                xc_mm,
+                yc_mm,
+                radius_mm,
+                width_mm,
+                clockwise,
+            } => {
+                let (start, end) =
+                    Self::arc_angles((x1_mm, y1_mm), (x2_mm, y2_mm), (xc_mm, yc_mm), clockwise);
+                let mut discs = vec![
+                    (x1_mm, y1_mm, width_mm / 2.0),
+                    (x2_mm, y2_mm, width_mm / 2.0),
+                ];
+                // The arc's extremes are at its ends and where it crosses an
+                // axis through its center
+                let mut angle = (start / FRAC_PI_2).ceil() * FRAC_PI_2;
+                while angle <= end {
+                    discs.push((
+                        xc_mm + radius_mm * angle.cos(),
+                        yc_mm + radius_mm * angle.sin(),
+                        width_mm / 2.0,
+                    ));
+                    angle += FRAC_PI_2;
+                }
+                discs
+            }
+            Self::Rectangle {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                ..
+            } => vec![
+                (x1_mm, y1_mm, 0.0),
+                (x2_mm, y1_mm, 0.0),
+                (x2_mm, y2_mm, 0.0),
+                (x1_mm, y2_mm, 0.0),
+            ],
+            Self::Circle {
+                x_mm,
+                y_mm,
+                diameter_mm,
+            } => vec![(x_mm, y_mm, diameter_mm / 2.0)],
+            Self::Square {
+                x_mm,
+                y_mm,
+                size_mm,
+            } => {
+                let h = size_mm / 2.0;
+                vec![
+                    (x_mm - h, y_mm - h, 0.0),
+                    (x_mm + h, y_mm - h, 0.0),
+                    (x_mm + h, y_mm + h, 0.0),
+                    (x_mm - h, y_mm + h, 0.0),
+                ]
+            }
+            Self::Oblong {
+                x_mm,
+                y_mm,
+                width_mm,
+                height_mm,
+            } => {
+                let r = width_mm.min(height_mm) / 2.0;
+                let (dx, dy) = (width_mm / 2.0 - r, height_mm / 2.0 - r);
+                vec![(x_mm - dx, y_mm - dy, r), (x_mm + dx, y_mm + dy, r)]
+            }
+            Self::Text { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the points along the primitive, with arcs flattened into
+    /// short segments. Lines and arcs run from their first point to their
+    /// second, and closed shapes end where they start. Text has no points.
+    pub fn path(&self) -> Vec<(f64, f64)> {
+        match *self {
+            Self::Line {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                ..
+            } => vec![(x1_mm, y1_mm), (x2_mm, y2_mm)],
+            Self::Arc {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                xc_mm,
+                yc_mm,
+                radius_mm,
+                clockwise,
+                ..
+            } => {
+                let (start, end) =
+                    Self::arc_angles((x1_mm, y1_mm), (x2_mm, y2_mm), (xc_mm, yc_mm), clockwise);
+                let steps = ((end - start) / ARC_STEP).ceil().max(1.0) as usize;
+                let mut points: Vec<(f64, f64)> = (1..steps)
+                    .map(|i| {
+                        let angle = start + (end - start) * i as f64 / steps as f64;
+                        (
+                            xc_mm + radius_mm * angle.cos(),
+                            yc_mm + radius_mm * angle.sin(),
+                        )
+                    })
+                    .collect();
+                if clockwise {
+                    points.reverse();
+                }
+                points.insert(0, (x1_mm, y1_mm));
+                points.push((x2_mm, y2_mm));
+                points
+            }
+            Self::Rectangle {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                ..
+            } => vec![
+                (x1_mm, y1_mm),
+                (x2_mm, y1_mm),
+                (x2_mm, y2_mm),
+                (x1_mm, y2_mm),
+                (x1_mm, y1_mm),
+            ],
+            Self::Circle {
+                x_mm,
+                y_mm,
+                diameter_mm,
+            } => {
+                let steps = (TAU / ARC_STEP).ceil() as usize;
+                (0..=steps)
+                    .map(|i| {
+                        let angle = TAU * i as f64 / steps as f64;
+                        (
+                            x_mm + diameter_mm / 2.0 * angle.cos(),
+                            y_mm + diameter_mm / 2.0 * angle.sin(),
+                        )
+                    })
+                    .collect()
+            }
+            Self::Square { .. } | Self::Oblong { .. } | Self::Text { .. } => Vec::new(),
+        }
+    }
+
+    /// Returns the length of a line or arc in millimeters, or `None` for
+    /// other primitives.
+    pub fn length_mm(&self) -> Option<f64> {
+        match *self {
+            Self::Line {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                ..
+            } => Some((x2_mm - x1_mm).hypot(y2_mm - y1_mm)),
+            Self::Arc {
+                x1_mm,
+                y1_mm,
+                x2_mm,
+                y2_mm,
+                xc_mm,
+                yc_mm,
+                radius_mm,
+                clockwise,
+                ..
+            } => {
+                let (start, end) =
+                    Self::arc_angles((x1_mm, y1_mm), (x2_mm, y2_mm), (xc_mm, yc_mm), clockwise);
+                Some(radius_mm * (end - start))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the axis-aligned bounding box of the primitive, or `None` for
+    /// text.
+    pub fn bounds(&self) -> Option<Rect> {
+        Rect::from_discs(self.extent_discs())
+    }
+}
+
+/// The largest angle, in radians, spanned by one segment of a flattened arc.
+const ARC_STEP: f64 = 5.0 * std::f64::consts::PI / 180.0;
+
+/// A closed polygon, in board coordinates and millimeters. The last point
+/// connects back to the first, and is not repeated.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polygon {
+    /// The vertices of the polygon, in order.
+    pub points: Vec<(f64, f64)>,
+}
+
+impl Polygon {
+    /// Returns the signed area of the polygon in square millimeters, which is
+    /// positive if its points run counter-clockwise.
+    pub fn signed_area_mm2(&self) -> f64 {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| {
+                let (x1, y1) = self.points[i];
+                let (x2, y2) = self.points[(i + 1) % n];
+                x1 * y2 - x2 * y1
+            })
+            .sum::<f64>()
+            / 2.0
+    }
+
+    /// Returns the area of the polygon in square millimeters.
+    pub fn area_mm2(&self) -> f64 {
+        self.signed_area_mm2().abs()
+    }
+
+    /// Returns the axis-aligned bounding box of the polygon.
+    pub fn bounds(&self) -> Option<Rect> {
+        Rect::from_discs(self.points.iter().map(|&(x, y)| (x, y, 0.0)))
+    }
+
+    /// Returns whether a point is inside the polygon.
+    pub fn contains(&self, x_mm: f64, y_mm: f64) -> bool {
+        let n = self.points.len();
+        let mut inside = false;
+        for i in 0..n {
+            let (x1, y1) = self.points[i];
+            let (x2, y2) = self.points[(i + 1) % n];
+            if (y1 > y_mm) != (y2 > y_mm) && x_mm < x1 + (y_mm - y1) / (y2 - y1) * (x2 - x1) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+/// Returns whether two points are within `tolerance` of each other.
+fn near((x1, y1): (f64, f64), (x2, y2): (f64, f64), tolerance: f64) -> bool {
+    (x1 - x2).hypot(y1 - y2) <= tolerance
+}
+
+/// Chains line and arc primitives whose ends meet into closed polygons.
+///
+/// Rectangles, circles, and full-circle arcs are closed polygons on their
+/// own. Chains that never close are dropped.
+///
+/// # Arguments
+///
+/// * `primitives` - The primitives to chain.
+/// * `tolerance_mm` - How far apart two ends may be and still meet.
+pub fn chain_polygons<'a>(
+    primitives: impl IntoIterator<Item = &'a Primitive>,
+    tolerance_mm: f64,
+) -> Vec<Polygon> {
+    chain_loops(primitives, tolerance_mm)
+        .into_iter()
+        .map(|(_, polygon)| polygon)
+        .collect()
+}
+
+/// Chains primitives into closed polygons as [chain_polygons] does, returning
+/// each polygon along with the indices of the primitives that form it.
+///
+/// The primitives' ends are joined into an [EndpointGraph], so the polygons
+/// found don't depend on the order of the primitives or their directions.
+pub(crate) fn chain_loops<'a>(
+    primitives: impl IntoIterator<Item = &'a Primitive>,
+    tolerance_mm: f64,
+) -> Vec<(Vec<usize>, Polygon)> {
+    let mut polygons = Vec::new();
+    let mut paths = Vec::new();
+
+    for (i, primitive) in primitives.into_iter().enumerate() {
+        let mut path = primitive.path();
+        if path.len() > 2 && near(path[0], path[path.len() - 1], tolerance_mm) {
+            path.pop();
+            polygons.push((vec![i], Polygon { points: path }));
+            // Closed on its own, so it isn't part of any other polygon
+            paths.push(Vec::new());
+        } else {
+            paths.push(path);
+        }
+    }
+
+    let graph = EndpointGraph::new(&paths, tolerance_mm);
+    for steps in graph.cycles() {
+        let mut points = join_paths(&paths, &steps);
+        points.pop();
+        if points.len() > 2 {
+            let indices = steps.iter().map(|&(i, _)| i).collect();
+            polygons.push((indices, Polygon { points }));
+        }
+    }
+
+    polygons
+}
+
+/// The ends of a set of paths, joined into nodes where they meet.
+///
+/// Walks through the graph are returned as steps, each of which is the index
+/// of a path and whether it's followed from its last point to its first.
+pub(crate) struct EndpointGraph {
+    /// The nodes at the first and last points of each path, or `None` for
+    /// paths with fewer than two points.
+    ends: Vec<Option<(usize, usize)>>,
+    /// The indices of the paths that end at each node. A path whose ends are
+    /// both at the node is listed twice.
+    adjacent: Vec<Vec<usize>>,
+}
+
+impl EndpointGraph {
+    /// Joins the ends of paths that are within `tolerance_mm` of each other.
+    pub(crate) fn new(paths: &[Vec<(f64, f64)>], tolerance_mm: f64) -> Self {
+        let cell_mm = tolerance_mm.max(1e-6);
+        let cell =
+            |(x, y): (f64, f64)| ((x / cell_mm).floor() as i64, (y / cell_mm).floor() as i64);
+
+        let mut points: Vec<(f64, f64)> = Vec::new();
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        let mut adjacent: Vec<Vec<usize>> = Vec::new();
+        let mut ends = Vec::with_capacity(paths.len());
+        for (i, path) in paths.iter().enumerate() {
+            if path.len() < 2 {
+                ends.push(None);
+                continue;
+            }
+
+            let mut nodes = [0; 2];
+            for (node, point) in nodes.iter_mut().zip([path[0], path[path.len() - 1]]) {
+                let (cx, cy) = cell(point);
+                let existing = (cx - 1..=cx + 1)
+                    .flat_map(|i| (cy - 1..=cy + 1).map(move |j| (i, j)))
+                    .filter_map(|key| grid.get(&key))
+                    .flatten()
+                    .copied()
+                    .filter(|&n| near(points[n], point, tolerance_mm))
+                    .min();
+                *node = existing.unwrap_or_else(|| {
+                    points.push(point);
+                    adjacent.push(Vec::new());
+                    grid.entry((cx, cy)).or_default().push(points.len() - 1);
+                    points.len() - 1
+                });
+                adjacent[*node].push(i);
+            }
+            ends.push(Some((nodes[0], nodes[1])));
+        }
+
+        Self { ends, adjacent }
+    }
+
+    /// Follows a path from one of its end nodes, returning the step and the
+    /// node at its other end.
+    fn step(&self, path: usize, from: usize) -> ((usize, bool), usize) {
+        let (first, last) = self.ends[path].unwrap_or_default();
+        if first == from {
+            ((path, false), last)
+        } else {
+            ((path, true), first)
+        }
+    }
+
+    /// Finds the closed loops in the graph.
+    ///
+    /// Paths that lead to a dead end can't be part of a loop, so they're
+    /// pruned first. Loops are then traced by walking the remaining paths,
+    /// starting from the lowest-numbered one, until the walk comes back to a
+    /// node it has already passed through. Every path is in at most one loop.
+    pub(crate) fn cycles(&self) -> Vec<Vec<(usize, bool)>> {
+        let mut used: Vec<bool> = self.ends.iter().map(Option::is_none).collect();
+        let mut degree: Vec<usize> = self.adjacent.iter().map(Vec::len).collect();
+        let mut leaves: Vec<usize> = (0..degree.len()).filter(|&n| degree[n] == 1).collect();
+        while let Some(leaf) = leaves.pop() {
+            let Some(&path) = self.adjacent[leaf].iter().find(|&&p| !used[p]) else {
+                continue;
+            };
+            used[path] = true;
+            let (_, other) = self.step(path, leaf);
+            degree[leaf] -= 1;
+            degree[other] -= 1;
+            if degree[other] == 1 {
+                leaves.push(other);
+            }
+        }
+
+        let mut cycles = Vec::new();
+        for first in 0..self.ends.len() {
+            if used[first] {
+                continue;
+            }
+
+            // The node each step starts from, followed by the current node
+            let mut nodes = vec![self.ends[first].unwrap_or_default().0];
+            let mut steps = Vec::new();
+            let mut next = Some(first);
+            while let Some(path) = next {
+                used[path] = true;
+                let (step, node) = self.step(path, nodes[nodes.len() - 1]);
+                steps.push(step);
+                match nodes.iter().position(|&n| n == node) {
+                    Some(k) => {
+                        cycles.push(steps.split_off(k));
+                        nodes.truncate(k + 1);
+                    }
+                    None => nodes.push(node),
+                }
+                next = self.adjacent[node].iter().copied().find(|&p| !used[p]);
+            }
+        }
+
+        cycles
+    }
+
+    /// Joins the paths into chains that pass through the nodes where exactly
+    /// two paths meet, so that every path is in one chain.
+    ///
+    /// Chains are returned in the order of their lowest-numbered paths, and
+    /// each one runs in the direction of that path.
+    pub(crate) fn chains(&self) -> Vec<Vec<(usize, bool)>> {
+        let mut used: Vec<bool> = self.ends.iter().map(Option::is_none).collect();
+        let mut chains = Vec::new();
+        for first in 0..self.ends.len() {
+            if used[first] {
+                continue;
+            }
+            used[first] = true;
+
+            let (start, end) = self.ends[first].unwrap_or_default();
+            let forward = self.extend(first, end, &mut used);
+            let backward = self.extend(first, start, &mut used);
+            let mut chain: Vec<(usize, bool)> = backward
+                .into_iter()
+                .rev()
+                .map(|(path, reversed)| (path, !reversed))
+                .collect();
+            chain.push((first, false));
+            chain.extend(forward);
+            chains.push(chain);
+        }
+        chains
+    }
+
+    /// Walks away from a path through nodes where exactly two paths meet,
+    /// until the walk reaches any other node or a used path.
+    fn extend(&self, mut from: usize, mut node: usize, used: &mut [bool]) -> Vec<(usize, bool)> {
+        let mut steps = Vec::new();
+        while let [a, b] = self.adjacent[node][..] {
+            let path = if a == from { b } else { a };
+            if used[path] {
+                break;
+            }
+            used[path] = true;
+            let (step, next) = self.step(path, node);
+            steps.push(step);
+            (from, node) = (path, next);
+        }
+        steps
+    }
+}
+
+/// Joins the paths of a walk through an [EndpointGraph] into one path.
+pub(crate) fn join_paths(paths: &[Vec<(f64, f64)>], steps: &[(usize, bool)]) -> Vec<(f64, f64)> {
+    let mut points: Vec<(f64, f64)> = Vec::new();
+    for &(path, reversed) in steps {
+        let mut path = paths[path].clone();
+        if reversed {
+            path.reverse();
+        }
+        let skip = usize::from(!points.is_empty());
+        points.extend(path.into_iter().skip(skip));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(values: &[&str]) -> [String; 9] {
+        std::array::from_fn(|i| values.get(i).unwrap_or(&"").to_string())
+    }
+
+    #[test]
+    fn test_primitive() {
+        let line =
+            Primitive::from_fields(&Units::Mils, "LINE", &fields(&["0", "0", "100", "0", "10"]))
+                .unwrap();
+        let bounds = line.bounds().unwrap();
+        assert!((bounds.max_x_mm - 2.667).abs() < 1e-9);
+        assert!((bounds.max_y_mm - 0.127).abs() < 1e-9);
+
+        // A counter-clockwise half circle through the top
+        let arc = Primitive::from_fields(
+            &Units::Millimeters,
+            "ARC",
+            &fields(&["1", "0", "-1", "0", "0", "0", "1", "", "COUNTERCLOCKWISE"]),
+        )
+        .unwrap();
+        let bounds = arc.bounds().unwrap();
+        assert!((bounds.max_y_mm - 1.0).abs() < 1e-9);
+        assert!(bounds.min_y_mm.abs() < 1e-9);
+
+        let text = Primitive::from_fields(
+            &Units::Millimeters,
+            "TEXT",
+            &fields(&["1", "2", "90", "NO", "CENTER", "1 0 0", "R1"]),
+        )
+        .unwrap();
+        assert!(matches!(text, Primitive::Text { ref text, .. } if text == "R1"));
+        assert_eq!(text.bounds(), None);
+
+        assert_eq!(
+            Primitive::from_fields(&Units::Mils, "BLOB", &fields(&[])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_chain_polygons() {
+        let line = |x1, y1, x2, y2| Primitive::Line {
+            x1_mm: x1,
+            y1_mm: y1,
+            x2_mm: x2,
+            y2_mm: y2,
+            width_mm: 0.0,
+        };
+        // A 10x10 square with one reversed edge and a little slop
+        let primitives = [
+            line(0.0, 0.0, 10.0, 0.0),
+            line(10.0, 10.0, 10.0, 0.001),
+            line(10.0, 10.0, 0.0, 10.0),
+            line(0.0, 10.0, 0.0, 0.0),
+            line(20.0, 20.0, 30.0, 30.0),
+        ];
+        let polygons = chain_polygons(&primitives, 0.01);
+        assert_eq!(polygons.len(), 1);
+        assert!((polygons[0].area_mm2() - 100.0).abs() < 0.1);
+        assert!(polygons[0].contains(5.0, 5.0));
+        assert!(!polygons[0].contains(15.0, 5.0));
+    }
+
+    #[test]
+    fn test_chain_loops_shuffled() {
+        let line = |x1, y1, x2, y2| Primitive::Line {
+            x1_mm: x1,
+            y1_mm: y1,
+            x2_mm: x2,
+            y2_mm: y2,
+            width_mm: 0.0,
+        };
+        // A triangle with a stub off one corner and a stray segment
+        let primitives = [
+            line(0.0, 0.0, 10.0, 0.0),
+            line(10.0, 0.0, 10.0, 10.0),
+            line(10.0, 10.0, 0.0, 0.0),
+            line(10.0, 10.0, 10.0, 15.0),
+            line(20.0, 0.0, 30.0, 0.0),
+        ];
+        for order in [
+            [0, 1, 2, 3, 4],
+            [3, 4, 2, 0, 1],
+            [2, 3, 0, 4, 1],
+            [4, 1, 3, 2, 0],
+        ] {
+            let shuffled: Vec<&Primitive> = order.iter().map(|&i| &primitives[i]).collect();
+            let loops = chain_loops(shuffled, 0.01);
+            assert_eq!(loops.len(), 1);
+            let mut indices: Vec<usize> = loops[0].0.iter().map(|&i| order[i]).collect();
+            indices.sort();
+            assert_eq!(indices, [0, 1, 2]);
+            assert!((loops[0].1.area_mm2() - 50.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_endpoint_graph_chains() {
+        // One path given out of order and with mixed directions, branching
+        // at (20, 0)
+        let paths = [
+            vec![(10.0, 0.0), (20.0, 0.0)],
+            vec![(30.0, 0.0), (20.0, 0.0)],
+            vec![(10.0, 0.0), (0.0, 0.0)],
+            vec![(20.0, 0.0), (20.0, 5.0)],
+        ];
+        let chains = EndpointGraph::new(&paths, 0.01).chains();
+        assert_eq!(chains.len(), 3);
+        assert_eq!(
+            join_paths(&paths, &chains[0]),
+            [(0.0, 0.0), (10.0, 0.0), (20.0, 0.0)]
+        );
+        assert_eq!(join_paths(&paths, &chains[1]), [(30.0, 0.0), (20.0, 0.0)]);
+        assert_eq!(join_paths(&paths, &chains[2]), [(20.0, 0.0), (20.0, 5.0)]);
+    }
+}
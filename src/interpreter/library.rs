@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/library.rs - Footprint library for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+
+use rust_decimal::Decimal;
+
+use super::Board;
+use super::FootprintInfo;
+use super::InterpretedPcbRepairFile;
+use super::PadShape;
+use super::PadType;
+use super::Pin;
+use super::Transform;
+
+/// A reference from a placed component to its footprint in a [Library].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LibraryRef {
+    /// The name of the footprint in [Library::footprints].
+    pub footprint: String,
+    /// The transform that places the footprint on the board.
+    pub transform: Transform,
+}
+
+/// A library of distinct footprints, one per package.
+///
+/// Footprints are grouped by symbol name. Components that share a symbol name
+/// but have different pad geometries get separate footprints, named after
+/// the symbol with a `_V2`, `_V3`, etc. suffix. A name that's already taken,
+/// such as by a symbol that's really named `R0402_V2`, is skipped in favor of
+/// the next free suffix.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Library {
+    /// The footprints in the library, by name. Their transforms are always
+    /// the identity.
+    pub footprints: BTreeMap<String, FootprintInfo>,
+    /// The library footprint of each component, by reference designator.
+    pub components: BTreeMap<String, LibraryRef>,
+}
+
+/// The geometry of one pad, as compared by [geometry_key].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct PadKey {
+    number: String,
+    x_mm: Decimal,
+    y_mm: Decimal,
+    radius_mm: Decimal,
+    shape: PadShape,
+    width_mm: Decimal,
+    height_mm: Decimal,
+    pad_type: PadType,
+    drill_mm: Option<Decimal>,
+}
+
+/// The geometry of a footprint's pads, rounded to the nearest micrometer so
+/// that footprints which only differ by rounding error compare equal.
+fn geometry_key(pins: &[Pin]) -> Vec<PadKey> {
+    let round = |d: Decimal| d.round_dp(3).normalize();
+    let mut key: Vec<_> = pins
+        .iter()
+        .map(|p| PadKey {
+            number: p.number.clone(),
+            x_mm: round(p.x_mm),
+            y_mm: round(p.y_mm),
+            radius_mm: round(p.radius_mm),
+            shape: p.shape,
+            width_mm: round(p.width_mm),
+            height_mm: round(p.height_mm),
+            pad_type: p.pad_type,
+            drill_mm: p.drill_mm.map(round),
+        })
+        .collect();
+    key.sort();
+    key
+}
+
+impl Library {
+    /// Builds a footprint library from a board model.
+    ///
+    /// Each component's footprint is first brought into its library
+    /// orientation, as with [InterpretedPcbRepairFile::from_board]. A library
+    /// footprint's description is the description shared by all of its
+    /// components, or an empty string if they differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    pub fn from_board(board: &Board) -> Self {
+        let interpreted = InterpretedPcbRepairFile::from_board(board);
+
+        let mut footprints: BTreeMap<String, FootprintInfo> = BTreeMap::new();
+        let mut variants: BTreeMap<&str, Vec<(String, Vec<_>)>> = BTreeMap::new();
+        let mut components = BTreeMap::new();
+
+        for (refdes, component) in &board.components {
+            let Some(info) = interpreted.footprints.get(refdes) else {
+                continue;
+            };
+
+            let base_name = if component.sym_name.is_empty() {
+                refdes.as_str()
+            } else {
+                component.sym_name.as_str()
+            };
+
+            let key = geometry_key(&info.pins);
+            let known = variants.entry(base_name).or_default();
+            let name = match known.iter().find(|(_, k)| *k == key) {
+                Some((name, _)) => {
+                    let footprint = footprints.get_mut(name).unwrap();
+                    if footprint.description != info.description {
+                        footprint.description.clear();
+                    }
+                    name.clone()
+                }
+                None => {
+                    let mut n = known.len() + 1;
+                    let mut name = match n {
+                        1 => base_name.to_string(),
+                        n => format!("{}_V{}", base_name, n),
+                    };
+                    while footprints.contains_key(&name) {
+                        n += 1;
+                        name = format!("{}_V{}", base_name, n);
+                    }
+                    footprints.insert(
+                        name.clone(),
+                        FootprintInfo {
+                            pins: info.pins.clone(),
+                            description: info.description.clone(),
+                            transform: Transform {
+                                rotation: 0,
+                                mirrored: false,
+                            },
+                        },
+                    );
+                    known.push((name.clone(), key));
+                    name
+                }
+            };
+
+            components.insert(
+                refdes.clone(),
+                LibraryRef {
+                    footprint: name,
+                    transform: info.transform,
+                },
+            );
+        }
+
+        Self {
+            footprints,
+            components,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{board_from, board_with_description};
+
+    #[test]
+    fn test_library_dedup() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!R1!1!R0402!NO!0!\n\
+S!R2!1!R0402!NO!90!\n\
+S!R3!1!R0402!YES!180!\n\
+S!R4!1!R0402!NO!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!R1!1!1!10!10!!0.25!\n\
+S!B!R1!2!2!11!10!!0.25!\n\
+S!A!R2!1!1!20!10!!0.25!\n\
+S!B!R2!2!2!20!11!!0.25!\n\
+S!A!R3!1!1!30!10!!0.25!\n\
+S!B!R3!2!2!31!10!!0.25!\n\
+S!A!R4!1!1!40!10!!0.25!\n\
+S!B!R4!2!2!42!10!!0.25!\n";
+        let description = b"BOARD|1.00|BOARD|1.00|PN\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\n\
+1\tRES 10K 1% 0402\t3\tR1 R2 R3\t\n\
+2\tRES 1K 1% 0402\t1\tR4\t\n";
+        let board = board_with_description(content, description);
+        let library = Library::from_board(&board);
+
+        assert_eq!(
+            library.footprints.keys().collect::<Vec<_>>(),
+            ["R0402", "R0402_V2"]
+        );
+        for refdes in ["R1", "R2", "R3"] {
+            assert_eq!(library.components[refdes].footprint, "R0402");
+        }
+        assert_eq!(library.components["R4"].footprint, "R0402_V2");
+        assert_eq!(library.components["R3"].transform.rotation, 180);
+        assert_eq!(library.footprints["R0402"].description, "RES 10K 1% 0402");
+    }
+
+    #[test]
+    fn test_library_pad_geometry_and_names() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!J1!1!HDR2!NO!0!\n\
+S!J2!1!HDR2!NO!0!\n\
+S!R1!1!R0402!NO!0!\n\
+S!R2!1!R0402!NO!0!\n\
+S!R3!1!R0402_V2!NO!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!J1!1!1!10!10!!0.5!\n\
+S!B!J1!2!2!12!10!!0.5!\n\
+S!A!J2!1!1!20!10!!0.5!\n\
+S!B!J2!2!2!22!10!!0.5!\n\
+S!A!R1!1!1!30!10!!0.25!\n\
+S!B!R1!2!2!31!10!!0.25!\n\
+S!A!R2!1!1!40!10!!0.25!\n\
+S!B!R2!2!2!42!10!!0.25!\n\
+S!A!R3!1!1!50!10!!0.25!\n\
+S!B!R3!2!2!51!10!!0.25!\n";
+        let mut board = board_from(content);
+
+        // Same positions and radii, but a different drill
+        for pin in &mut board.components.get_mut("J2").unwrap().pins {
+            pin.pad_type = PadType::ThroughHole;
+            pin.drill_mm = Some(Decimal::new(8, 1));
+        }
+        let library = Library::from_board(&board);
+
+        assert_eq!(library.components["J1"].footprint, "HDR2");
+        assert_eq!(library.components["J2"].footprint, "HDR2_V2");
+
+        // R3's symbol name is already taken by R2's variant
+        assert_eq!(library.components["R2"].footprint, "R0402_V2");
+        assert_eq!(library.components["R3"].footprint, "R0402_V2_V2");
+        assert_eq!(library.footprints.len(), 5);
+    }
+}
@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/netinfo.rs - Net name analysis for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use rust_decimal::Decimal;
+
+/// A power state that a rail is powered in, as marked by its name. States
+/// are ordered from the least to the most always-on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerState {
+    /// Powered only while the system is running (e.g., "S0", "RUN", or a
+    /// trailing "VS" as in "+3VS").
+    S0,
+    /// Powered in suspend-to-RAM and above (e.g., "S3").
+    S3,
+    /// Powered in suspend (e.g., "SUS").
+    Sus,
+    /// Powered in soft-off and above (e.g., "S5", "DSW").
+    S5,
+    /// Always powered while any power source is present (e.g., "ALW",
+    /// "AUX").
+    Always,
+}
+
+/// The polarity of one half of a differential pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Polarity {
+    /// The positive half (e.g., "_P", "_DP", or "+").
+    Positive,
+    /// The negative half (e.g., "_N", "_DN", or "-").
+    Negative,
+}
+
+/// One half of a differential pair.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffPair {
+    /// The polarity of this half.
+    pub polarity: Polarity,
+    /// The name of the other half.
+    pub partner: String,
+}
+
+/// A member of a bus of nets that share a name and differ by index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BusMember {
+    /// The name of the bus, which is the net name without its index (e.g.,
+    /// "DQ" for "DQ12").
+    pub bus: String,
+    /// The index of the net in the bus.
+    pub index: u32,
+}
+
+/// What a net's name says about the net.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetInfo {
+    /// The nominal voltage of the net, e.g. 1.05 for "+1V05".
+    pub voltage: Option<Decimal>,
+    /// Whether the net is a ground net.
+    pub ground: bool,
+    /// The power state the net is powered in, if its name has a suffix for
+    /// one.
+    pub power_state: Option<PowerState>,
+    /// The differential pair the net is a half of, if any.
+    pub diff_pair: Option<DiffPair>,
+    /// The bus the net is a member of, if any.
+    pub bus: Option<BusMember>,
+}
+
+/// Parses the nominal voltage out of a net name, e.g. 3.3 from "+3V3",
+/// "VCC_3.3V", or "P3P3V".
+fn parse_voltage(upper: &str) -> Option<Decimal> {
+    let chars: Vec<char> = upper.chars().collect();
+    let digits = |start: usize| {
+        chars[start..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        if !chars[i].is_ascii_digit() || (i > 0 && chars[i - 1].is_ascii_digit()) {
+            i += 1;
+            continue;
+        }
+
+        let int_len = digits(i);
+        let integer: String = chars[i..i + int_len].iter().collect();
+        let mut j = i + int_len;
+        let mut fraction = String::new();
+        if j < chars.len() && (chars[j] == '.' || chars[j] == 'P') && digits(j + 1) > 0 {
+            let frac_len = digits(j + 1);
+            let sep = chars[j];
+            fraction = chars[j + 1..j + 1 + frac_len].iter().collect();
+            j += 1 + frac_len;
+            if sep == 'P' && chars.get(j) != Some(&'V') {
+                i = j;
+                continue;
+            }
+        }
+        if chars.get(j) == Some(&'V') {
+            if fraction.is_empty() {
+                fraction = chars[j + 1..j + 1 + digits(j + 1)].iter().collect();
+            }
+            let text = if fraction.is_empty() {
+                integer
+            } else {
+                format!("{}.{}", integer, fraction)
+            };
+            if let Ok(voltage) = text.parse::<Decimal>() {
+                return Some(voltage.normalize());
+            }
+        }
+        i = j.max(i + 1);
+    }
+
+    None
+}
+
+/// Splits text into its non-empty words.
+///
+/// # Arguments
+///
+/// * `text` - The text to split.
+/// * `is_separator` - Whether a character separates two words.
+pub(crate) fn words(text: &str, is_separator: impl Fn(char) -> bool) -> impl Iterator<Item = &str> {
+    text.split(is_separator).filter(|w| !w.is_empty())
+}
+
+/// Returns whether a character separates the words of a name, i.e. whether
+/// it isn't alphanumeric.
+pub(crate) fn is_word_separator(c: char) -> bool {
+    !c.is_ascii_alphanumeric()
+}
+
+fn is_ground(upper: &str) -> bool {
+    const GROUND_WORDS: &[&str] = &["GND", "AGND", "DGND", "PGND", "SGND", "CGND", "GROUND"];
+    matches!(upper, "VSS" | "VSSA")
+        || words(upper, is_word_separator).any(|w| GROUND_WORDS.contains(&w))
+}
+
+fn power_state(upper: &str, has_voltage: bool) -> Option<PowerState> {
+    let state = |word: &str| match word {
+        "S0" | "RUN" => Some(PowerState::S0),
+        "S3" => Some(PowerState::S3),
+        "S5" | "DSW" => Some(PowerState::S5),
+        "SUS" => Some(PowerState::Sus),
+        "ALW" | "AUX" => Some(PowerState::Always),
+        _ => None,
+    };
+
+    if let Some(state) = words(upper, is_word_separator).filter_map(state).last() {
+        return Some(state);
+    }
+
+    // Suffixes run together with the voltage, as in "+3VALW" or "+5VS"
+    if has_voltage {
+        for (suffix, state) in [
+            ("VALW", PowerState::Always),
+            ("VAUX", PowerState::Always),
+            ("VSUS", PowerState::Sus),
+            ("VS", PowerState::S0),
+        ] {
+            if upper.ends_with(suffix) {
+                return Some(state);
+            }
+        }
+    }
+
+    None
+}
+
+fn diff_pair(name: &str) -> Option<DiffPair> {
+    const SUFFIXES: &[(&str, &str, Polarity)] = &[
+        ("_DP", "_DN", Polarity::Positive),
+        ("_DN", "_DP", Polarity::Negative),
+        ("_P", "_N", Polarity::Positive),
+        ("_N", "_P", Polarity::Negative),
+        ("+", "-", Polarity::Positive),
+        ("-", "+", Polarity::Negative),
+    ];
+
+    SUFFIXES.iter().find_map(|(suffix, other, polarity)| {
+        // Match case-insensitively, but keep the case of the partner's name
+        let split = name.len().checked_sub(suffix.len())?;
+        let (base, end) = (name.get(..split)?, &name[split..]);
+        if base.is_empty() || !end.eq_ignore_ascii_case(suffix) {
+            return None;
+        }
+        let other = if end.chars().any(|c| c.is_ascii_lowercase()) {
+            other.to_ascii_lowercase()
+        } else {
+            other.to_string()
+        };
+        Some(DiffPair {
+            polarity: *polarity,
+            partner: format!("{}{}", base, other),
+        })
+    })
+}
+
+fn bus_member(name: &str) -> Option<BusMember> {
+    let trimmed = name.strip_suffix(']').unwrap_or(name);
+    let base = trimmed.trim_end_matches(|c: char| c.is_ascii_digit());
+    let index = trimmed[base.len()..].parse().ok()?;
+    let base = if trimmed.len() != name.len() {
+        base.strip_suffix('[')?
+    } else {
+        base
+    };
+    if !base.ends_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        return None;
+    }
+
+    Some(BusMember {
+        bus: base.to_string(),
+        index,
+    })
+}
+
+impl NetInfo {
+    /// Analyzes a net name.
+    ///
+    /// This only looks at the name itself, so a net is reported as half of a
+    /// differential pair or as a bus member even if the rest of the pair or
+    /// bus doesn't exist. [Netlist](super::Netlist) only keeps pairs whose
+    /// partner exists and buses with at least two members.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the net.
+    pub fn from_name(name: &str) -> Self {
+        let upper = name.to_ascii_uppercase();
+        let ground = is_ground(&upper);
+        let voltage = if ground { None } else { parse_voltage(&upper) };
+
+        Self {
+            voltage,
+            ground,
+            power_state: power_state(&upper, voltage.is_some()),
+            diff_pair: diff_pair(name),
+            bus: match voltage.is_some() || ground {
+                true => None,
+                false => bus_member(name),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_net_info() {
+        let voltage = |name| NetInfo::from_name(name).voltage.map(|v| v.to_string());
+        assert_eq!(voltage("+1V05").as_deref(), Some("1.05"));
+        assert_eq!(voltage("+3.3V").as_deref(), Some("3.3"));
+        assert_eq!(voltage("P1V8").as_deref(), Some("1.8"));
+        assert_eq!(voltage("P1P8V").as_deref(), Some("1.8"));
+        assert_eq!(voltage("DDR4_DQ0"), None);
+
+        assert!(NetInfo::from_name("GND").ground);
+        assert!(NetInfo::from_name("AGND_AUDIO").ground);
+        assert!(!NetInfo::from_name("GNDSENSE").ground);
+
+        let state = |name| NetInfo::from_name(name).power_state;
+        assert_eq!(state("PP5V_S0"), Some(PowerState::S0));
+        assert_eq!(state("+3VALW"), Some(PowerState::Always));
+        assert_eq!(state("+1.2V_SUS"), Some(PowerState::Sus));
+        assert_eq!(state("+5VS"), Some(PowerState::S0));
+        assert_eq!(state("+5V"), None);
+
+        let pair = NetInfo::from_name("USB_D+").diff_pair.unwrap();
+        assert_eq!(pair.polarity, Polarity::Positive);
+        assert_eq!(pair.partner, "USB_D-");
+        let pair = NetInfo::from_name("PCIE_TX0_n").diff_pair.unwrap();
+        assert_eq!(pair.polarity, Polarity::Negative);
+        assert_eq!(pair.partner, "PCIE_TX0_p");
+
+        let bus = NetInfo::from_name("M_A_DQ63").bus.unwrap();
+        assert_eq!((bus.bus.as_str(), bus.index), ("M_A_DQ", 63));
+        let bus = NetInfo::from_name("SA[12]").bus.unwrap();
+        assert_eq!((bus.bus.as_str(), bus.index), ("SA", 12));
+        assert_eq!(NetInfo::from_name("+3V3").bus, None);
+    }
+}
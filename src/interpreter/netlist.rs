@@ -0,0 +1,264 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/netlist.rs - Netlist model for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use super::Board;
+use super::NetInfo;
+
+/// A reference to a pin of a placed component.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PinRef {
+    /// The reference designator of the component.
+    pub refdes: String,
+    /// The number of the pin.
+    pub pin: String,
+}
+
+/// A net and everything connected to it.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Net {
+    /// The name of the net.
+    pub name: String,
+    /// The component pins on the net.
+    pub pins: Vec<PinRef>,
+    /// The indices of the test vias on the net in [Board::testvias].
+    pub testvias: Vec<usize>,
+    /// Whether the net's name marks its pins as not connected (e.g., "NC").
+    /// Such pins aren't actually connected to each other.
+    pub no_connect: bool,
+    /// Whether only a single component pin is on the net.
+    pub single_pin: bool,
+    /// What the net's name says about the net.
+    pub info: NetInfo,
+}
+
+/// The connectivity of the board, indexed by net and by pin.
+///
+/// Pins with an empty net name aren't on any net.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Netlist {
+    /// The nets on the board, by name.
+    pub nets: BTreeMap<String, Net>,
+    /// The net of each pin, by reference designator and then pin number.
+    pub pin_nets: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// Returns whether a net name marks its pins as not connected.
+fn is_no_connect(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    matches!(upper.as_str(), "NC" | "N/C" | "N.C." | "NO_CONNECT")
+        || upper.starts_with("NC_")
+        || upper.starts_with("UNCONNECTED")
+}
+
+fn net_entry<'a>(nets: &'a mut BTreeMap<String, Net>, name: &str) -> &'a mut Net {
+    nets.entry(name.to_string()).or_insert_with(|| Net {
+        name: name.to_string(),
+        pins: Vec::new(),
+        testvias: Vec::new(),
+        no_connect: is_no_connect(name),
+        single_pin: false,
+        info: NetInfo::from_name(name),
+    })
+}
+
+impl Netlist {
+    /// Builds the netlist of a board.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    pub fn from_board(board: &Board) -> Self {
+        let mut nets: BTreeMap<String, Net> = BTreeMap::new();
+        let mut pin_nets: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+
+        for (refdes, component) in &board.components {
+            for pin in &component.pins {
+                if pin.net_name.is_empty() {
+                    continue;
+                }
+
+                net_entry(&mut nets, &pin.net_name).pins.push(PinRef {
+                    refdes: refdes.clone(),
+                    pin: pin.number.clone(),
+                });
+                pin_nets
+                    .entry(refdes.clone())
+                    .or_default()
+                    .insert(pin.number.clone(), pin.net_name.clone());
+            }
+        }
+
+        for (index, testvia) in board.testvias.iter().enumerate() {
+            if !testvia.net_name.is_empty() {
+                net_entry(&mut nets, &testvia.net_name).testvias.push(index);
+            }
+        }
+
+        let mut bus_sizes: BTreeMap<String, usize> = BTreeMap::new();
+        for net in nets.values() {
+            if let Some(member) = &net.info.bus {
+                *bus_sizes.entry(member.bus.clone()).or_default() += 1;
+            }
+        }
+        let names: BTreeSet<String> = nets.keys().cloned().collect();
+        for net in nets.values_mut() {
+            net.single_pin = net.pins.len() == 1;
+            if let Some(pair) = &net.info.diff_pair
+                && !names.contains(&pair.partner)
+            {
+                net.info.diff_pair = None;
+            }
+            if let Some(member) = &net.info.bus
+                && bus_sizes[&member.bus] < 2
+            {
+                net.info.bus = None;
+            }
+        }
+
+        Self { nets, pin_nets }
+    }
+
+    /// Returns the net with the given name.
+    pub fn net(&self, name: &str) -> Option<&Net> {
+        self.nets.get(name)
+    }
+
+    /// Returns the name of the net a pin is connected to.
+    ///
+    /// # Arguments
+    ///
+    /// * `refdes` - The reference designator of the pin's component.
+    /// * `pin` - The number of the pin.
+    pub fn net_of(&self, refdes: &str, pin: &str) -> Option<&str> {
+        self.pin_nets
+            .get(refdes)
+            .and_then(|pins| pins.get(pin))
+            .map(String::as_str)
+    }
+
+    /// Returns the names of the nets a component is connected to, in sorted
+    /// order and without duplicates.
+    pub fn component_nets(&self, refdes: &str) -> Vec<&str> {
+        let names: BTreeSet<&str> = self
+            .pin_nets
+            .get(refdes)
+            .into_iter()
+            .flat_map(|pins| pins.values().map(String::as_str))
+            .collect();
+        names.into_iter().collect()
+    }
+
+    /// Returns the nets in a bus, in index order.
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - The name of the bus (see [BusMember::bus](super::BusMember::bus)).
+    pub fn bus(&self, bus: &str) -> Vec<&Net> {
+        let mut members: Vec<&Net> = self
+            .nets
+            .values()
+            .filter(|net| net.info.bus.as_ref().is_some_and(|m| m.bus == bus))
+            .collect();
+        members.sort_by_key(|net| net.info.bus.as_ref().map(|m| m.index));
+        members
+    }
+
+    /// Returns the number of component pins on a net, or zero if there is no
+    /// such net.
+    pub fn pin_count(&self, name: &str) -> usize {
+        self.net(name).map_or(0, |net| net.pins.len())
+    }
+
+    /// Returns the other pins on the same net as the given pin. Pins on a
+    /// [no-connect](Net::no_connect) net aren't connected to anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `refdes` - The reference designator of the pin's component.
+    /// * `pin` - The number of the pin.
+    pub fn connected_pins(&self, refdes: &str, pin: &str) -> Vec<&PinRef> {
+        let Some(net) = self.net_of(refdes, pin).and_then(|name| self.net(name)) else {
+            return Vec::new();
+        };
+        if net.no_connect {
+            return Vec::new();
+        }
+
+        net.pins
+            .iter()
+            .filter(|p| p.refdes != refdes || p.pin != pin)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_netlist() {
+        let content = b"A!UNIT!millimeters!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!+3V3!U1!1!VCC!0!0!!0.25!\n\
+S!GND!U1!2!GND!1!0!!0.25!\n\
+S!NC!U1!3!NC1!2!0!!0.25!\n\
+S!!U1!4!NC2!3!0!!0.25!\n\
+S!NC!U1!5!NC3!4!0!!0.25!\n\
+S!+3V3!C1!1!1!0!5!!0.25!\n\
+S!GND!C1!2!2!1!5!!0.25!\n\
+S!LED_K!D1!2!K!8!8!!0.25!\n\
+S!D1!U2!1!1!0!9!!0.25!\n\
+S!D0!U2!2!2!1!9!!0.25!\n\
+S!CLK_P!U2!3!3!2!9!!0.25!\n\
+S!CLK_N!U2!4!4!3!9!!0.25!\n\
+S!REQ_P!U2!5!5!4!9!!0.25!\n\
+A!TESTVIA!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!VIA_X!VIA_Y!TEST_POINT!RADIUS!\n\
+S!TP1!+3V3!!!!3!3!T!0.5!\n";
+        let board = board_from(content);
+        let netlist = Netlist::from_board(&board);
+
+        assert_eq!(netlist.net_of("C1", "1"), Some("+3V3"));
+        assert_eq!(netlist.net_of("U1", "4"), None);
+        assert_eq!(netlist.component_nets("U1"), ["+3V3", "GND", "NC"]);
+        assert_eq!(netlist.pin_count("GND"), 2);
+        assert_eq!(netlist.net("+3V3").unwrap().testvias, [0]);
+        assert!(netlist.net("NC").unwrap().no_connect);
+        assert!(netlist.connected_pins("U1", "3").is_empty());
+        assert!(netlist.net("LED_K").unwrap().single_pin);
+        assert!(netlist.net("CLK_N").unwrap().info.diff_pair.is_some());
+        assert!(netlist.net("REQ_P").unwrap().info.diff_pair.is_none());
+        let bus: Vec<_> = netlist.bus("D").iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(bus, ["D0", "D1"]);
+        assert_eq!(
+            netlist.connected_pins("U1", "1"),
+            [&PinRef {
+                refdes: "C1".into(),
+                pin: "1".into()
+            }]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/outline.rs - Board outline for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use super::Board;
+use super::Polygon;
+use super::Rect;
+use super::chain_polygons;
+
+/// How far apart, in millimeters, the ends of two outline segments may be
+/// and still be chained together.
+const OUTLINE_TOLERANCE_MM: f64 = 0.01;
+
+/// The edge of the board.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardOutline {
+    /// The outer edge of the board.
+    pub outer: Polygon,
+    /// The cutouts and holes inside the outer edge.
+    pub cutouts: Vec<Polygon>,
+    /// The bounding box of the outer edge.
+    pub bounds: Rect,
+    /// The area of the board, not counting the cutouts, in square
+    /// millimeters.
+    pub area_mm2: f64,
+    /// Whether the outline was estimated from the convex hull of the pins and
+    /// vias because the file has no usable outline graphics.
+    pub estimated: bool,
+}
+
+/// Returns whether a graphic's class and subclass mark it as part of the
+/// board outline, and if so, whether it's a cutout.
+fn outline_role(class: &str, subclass: &str) -> Option<bool> {
+    let class = class.to_ascii_uppercase();
+    if !class.is_empty() && class != "BOARD GEOMETRY" {
+        return None;
+    }
+    match subclass.to_ascii_uppercase().as_str() {
+        "OUTLINE" | "DESIGN_OUTLINE" => Some(false),
+        "CUTOUT" => Some(true),
+        _ => None,
+    }
+}
+
+/// Returns the convex hull of a set of points, counter-clockwise.
+fn convex_hull(mut points: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let cross = |o: (f64, f64), a: (f64, f64), b: (f64, f64)| {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    };
+    let mut hull: Vec<(f64, f64)> = Vec::new();
+    for pass in [false, true] {
+        let start = hull.len();
+        let ordered: Box<dyn Iterator<Item = &(f64, f64)>> = if pass {
+            Box::new(points.iter().rev())
+        } else {
+            Box::new(points.iter())
+        };
+        for &point in ordered {
+            while hull.len() >= start + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        hull.pop();
+    }
+    hull
+}
+
+impl BoardOutline {
+    /// Assembles the board outline from the board's graphics.
+    ///
+    /// Line and arc segments in the OUTLINE and DESIGN_OUTLINE subclasses of
+    /// the BOARD GEOMETRY class are chained into closed polygons. The largest
+    /// is the outer edge, and the others inside it are cutouts, along with
+    /// any polygons in the CUTOUT subclass. Polygons outside the outer edge
+    /// are ignored.
+    ///
+    /// If there are no closed outline polygons, the outline is estimated from
+    /// the convex hull of the pins, vias, and test vias.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    ///
+    /// # Returns
+    ///
+    /// The outline, or `None` if the board has neither outline graphics nor
+    /// anything to estimate the outline from.
+    pub fn from_board(board: &Board) -> Option<Self> {
+        let edges = board
+            .graphics
+            .iter()
+            .filter(|g| outline_role(&g.class, &g.subclass) == Some(false))
+            .map(|g| &g.primitive);
+        let cutout_edges = board
+            .graphics
+            .iter()
+            .filter(|g| outline_role(&g.class, &g.subclass) == Some(true))
+            .map(|g| &g.primitive);
+
+        let mut polygons = chain_polygons(edges, OUTLINE_TOLERANCE_MM);
+        if polygons.is_empty() {
+            return Self::estimate(board);
+        }
+
+        polygons.sort_by(|a, b| b.area_mm2().total_cmp(&a.area_mm2()));
+        let outer = polygons.remove(0);
+        let cutouts: Vec<Polygon> = polygons
+            .into_iter()
+            .chain(chain_polygons(cutout_edges, OUTLINE_TOLERANCE_MM))
+            .filter(|p| p.points.iter().all(|&(x, y)| outer.contains(x, y)))
+            .collect();
+
+        let area_mm2 = outer.area_mm2() - cutouts.iter().map(Polygon::area_mm2).sum::<f64>();
+        Some(Self {
+            bounds: outer.bounds()?,
+            outer,
+            cutouts,
+            area_mm2,
+            estimated: false,
+        })
+    }
+
+    /// Estimates the outline from the convex hull of the pins, vias, and test
+    /// vias.
+    fn estimate(board: &Board) -> Option<Self> {
+        let f = |value: Decimal| value.to_f64().unwrap_or(0.0);
+        let discs = board
+            .components
+            .values()
+            .flat_map(|c| c.pins.iter().map(|p| (p.x_mm, p.y_mm, p.radius_mm)))
+            .chain(
+                board
+                    .vias
+                    .iter()
+                    .map(|v| (v.x_mm, v.y_mm, v.radius_mm.unwrap_or_default())),
+            )
+            .chain(board.testvias.iter().map(|t| (t.x_mm, t.y_mm, t.radius_mm)));
+
+        let mut points = Vec::new();
+        for (x, y, r) in discs {
+            let (x, y, r) = (f(x), f(y), f(r));
+            points.extend([
+                (x - r, y - r),
+                (x + r, y - r),
+                (x + r, y + r),
+                (x - r, y + r),
+            ]);
+        }
+
+        let outer = Polygon {
+            points: convex_hull(points),
+        };
+        Some(Self {
+            bounds: outer.bounds()?,
+            area_mm2: outer.area_mm2(),
+            outer,
+            cutouts: Vec::new(),
+            estimated: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_board_outline() {
+        let board = board_from(
+            b"A!UNIT!millimeters!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!BOARD GEOMETRY!OUTLINE!LINE!1!1 1 0!0!0!100!0!0!!!!!!\n\
+S!BOARD GEOMETRY!OUTLINE!LINE!1!2 1 0!100!0!100!50!0!!!!!!\n\
+S!BOARD GEOMETRY!OUTLINE!LINE!1!3 1 0!0!50!100!50!0!!!!!!\n\
+S!BOARD GEOMETRY!OUTLINE!LINE!1!4 1 0!0!50!0!0!0!!!!!!\n\
+S!BOARD GEOMETRY!OUTLINE!ARC!2!1 1 0!10!10!10!10!12!10!2!0!COUNTERCLOCKWISE!!\n\
+S!BOARD GEOMETRY!SILKSCREEN_TOP!LINE!3!1 1 0!0!0!500!500!0!!!!!!\n",
+        );
+        let outline = BoardOutline::from_board(&board).unwrap();
+        assert!(!outline.estimated);
+        assert_eq!(outline.cutouts.len(), 1);
+        assert_eq!(
+            (outline.bounds.max_x_mm, outline.bounds.max_y_mm),
+            (100.0, 50.0)
+        );
+        let hole = std::f64::consts::PI * 4.0;
+        assert!((outline.area_mm2 - (5000.0 - hole)).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_estimated_outline() {
+        let board = board_from(
+            b"A!UNIT!millimeters!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!U1!1!1!0!0!!1!\n\
+S!A!U1!2!2!10!0!!1!\n\
+S!A!U1!3!3!5!5!!1!\n\
+S!A!U1!4!4!5!1!!1!\n",
+        );
+        let outline = BoardOutline::from_board(&board).unwrap();
+        assert!(outline.estimated);
+        assert_eq!(outline.bounds.min_x_mm, -1.0);
+        assert_eq!(outline.bounds.max_y_mm, 6.0);
+        assert_eq!(outline.outer.points.len(), 6);
+    }
+}
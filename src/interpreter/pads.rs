@@ -0,0 +1,605 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/pads.rs - Pad geometry inference for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use super::BoardGraphic;
+use super::BoardPin;
+use super::ComponentKind;
+use super::PlacedComponent;
+use super::Primitive;
+use super::Rect;
+use super::Side;
+use super::chain_polygons;
+
+/// The shape of a pad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PadShape {
+    /// A circle, with equal width and height.
+    Circle,
+    /// A rectangle with sharp corners.
+    Rect,
+    /// A rectangle with rounded corners.
+    RoundRect,
+    /// A stadium (obround), with fully rounded ends.
+    Oval,
+}
+
+/// How a pad is mounted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PadType {
+    /// A surface-mount pad on one side of the board.
+    Smd,
+    /// A plated hole through the board.
+    ThroughHole,
+}
+
+/// How far a pad's center may be from its pin and still match it, in
+/// millimeters.
+const PAD_CENTER_TOLERANCE_MM: f64 = 0.05;
+
+/// The size of an inferred pad, as a fraction of the pitch to the nearest
+/// pin.
+const INFERRED_PAD_PITCH_RATIO: Decimal = Decimal::from_parts(5, 0, 0, false, 1);
+
+/// How far the ends of a pad outline's segments may be apart and still
+/// meet, in millimeters.
+const PAD_OUTLINE_TOLERANCE_MM: f64 = 0.001;
+
+/// The drill diameter estimated for a through-hole pin without a drill
+/// graphic, as a fraction of its pad's smaller dimension.
+const ESTIMATED_DRILL_PAD_RATIO: Decimal = Decimal::from_parts(6, 0, 0, false, 1);
+
+/// Words in a component's description that mark it as a through-hole part.
+const THROUGH_HOLE_WORDS: [&str; 6] = ["DIP", "THT", "THD", "PTH", "THRU", "THROUGH"];
+
+/// A pad or drill outline found in the graphics.
+struct PadOutline<'a> {
+    graphic: &'a BoardGraphic,
+    shape: PadShape,
+    bounds: Rect,
+    /// Whether the outline is a drill hole rather than a pad.
+    drill: bool,
+}
+
+impl PadOutline<'_> {
+    /// Returns the side of the board the outline's layer is on, if it's
+    /// specific to one.
+    fn side(&self) -> Option<Side> {
+        let layer =
+            format!("{} {}", self.graphic.class, self.graphic.subclass).to_ascii_uppercase();
+        if layer.contains("BOTTOM") {
+            Some(Side::Bottom)
+        } else if layer.contains("TOP") {
+            Some(Side::Top)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns whether a graphic is on a drill or hole layer.
+fn is_drill_graphic(graphic: &BoardGraphic) -> bool {
+    [&graphic.class, &graphic.subclass].iter().any(|name| {
+        let upper = name.to_ascii_uppercase();
+        upper.contains("DRILL") || upper.contains("HOLE")
+    })
+}
+
+/// Returns whether a graphic is on a pin or pad layer.
+fn is_pad_graphic(graphic: &BoardGraphic) -> bool {
+    [&graphic.class, &graphic.subclass].iter().any(|name| {
+        let upper = name.to_ascii_uppercase();
+        upper.starts_with("PIN") || upper.starts_with("PAD")
+    })
+}
+
+/// Classifies a closed figure of lines and arcs by how its corners are
+/// rounded.
+fn figure_shape(figure: &[&BoardGraphic], bounds: &Rect) -> PadShape {
+    let radii: Vec<f64> = figure
+        .iter()
+        .filter_map(|g| match g.primitive {
+            Primitive::Arc { radius_mm, .. } => Some(radius_mm),
+            _ => None,
+        })
+        .collect();
+    let has_lines = figure
+        .iter()
+        .any(|g| matches!(g.primitive, Primitive::Line { .. }));
+    let half_min = bounds.width_mm().min(bounds.height_mm()) / 2.0;
+
+    match (radii.is_empty(), has_lines) {
+        (true, _) => PadShape::Rect,
+        (false, false) => PadShape::Circle,
+        _ if radii
+            .iter()
+            .all(|r| (r - half_min).abs() <= PAD_OUTLINE_TOLERANCE_MM) =>
+        {
+            PadShape::Oval
+        }
+        _ => PadShape::RoundRect,
+    }
+}
+
+/// Collects the pad and drill outlines from a board's graphics.
+fn pad_outlines(graphics: &[BoardGraphic]) -> Vec<PadOutline<'_>> {
+    let mut outlines = Vec::new();
+    let mut figures: BTreeMap<(&str, &str, &str, &str, u64), Vec<&BoardGraphic>> = BTreeMap::new();
+
+    for graphic in graphics
+        .iter()
+        .filter(|g| is_drill_graphic(g) || is_pad_graphic(g))
+    {
+        let Some(bounds) = graphic.primitive.bounds() else {
+            continue;
+        };
+        let shape = match graphic.primitive {
+            Primitive::Rectangle { .. } | Primitive::Square { .. } => PadShape::Rect,
+            Primitive::Circle { .. } => PadShape::Circle,
+            Primitive::Oblong {
+                width_mm,
+                height_mm,
+                ..
+            } if width_mm == height_mm => PadShape::Circle,
+            Primitive::Oblong { .. } => PadShape::Oval,
+            Primitive::Line { .. } | Primitive::Arc { .. } => {
+                let key = (
+                    graphic.class.as_str(),
+                    graphic.subclass.as_str(),
+                    graphic.refdes.as_str(),
+                    graphic.sym_name.as_str(),
+                    graphic.number,
+                );
+                figures.entry(key).or_default().push(graphic);
+                continue;
+            }
+            Primitive::Text { .. } => continue,
+        };
+        outlines.push(PadOutline {
+            graphic,
+            shape,
+            bounds,
+            drill: is_drill_graphic(graphic),
+        });
+    }
+
+    for figure in figures.values() {
+        let primitives = figure.iter().map(|g| &g.primitive);
+        for polygon in chain_polygons(primitives, PAD_OUTLINE_TOLERANCE_MM) {
+            let Some(bounds) = polygon.bounds() else {
+                continue;
+            };
+            outlines.push(PadOutline {
+                graphic: figure[0],
+                shape: figure_shape(figure, &bounds),
+                bounds,
+                drill: is_drill_graphic(figure[0]),
+            });
+        }
+    }
+
+    outlines
+}
+
+/// The pad and drill outlines of a board, indexed by location.
+pub(crate) struct PadIndex<'a> {
+    outlines: Vec<PadOutline<'a>>,
+    /// The indices of the outlines by the millimeter grid cell their center
+    /// is in.
+    grid: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl<'a> PadIndex<'a> {
+    /// Indexes the pad and drill outlines in a board's graphics.
+    pub(crate) fn new(graphics: &'a [BoardGraphic]) -> Self {
+        let outlines = pad_outlines(graphics);
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (index, outline) in outlines.iter().enumerate() {
+            let (x, y) = outline.bounds.center();
+            grid.entry(Self::cell(x, y)).or_default().push(index);
+        }
+        Self { outlines, grid }
+    }
+
+    fn cell(x: f64, y: f64) -> (i64, i64) {
+        (x.floor() as i64, y.floor() as i64)
+    }
+
+    /// Returns the outlines centered on a pin that belong to its component
+    /// or to no component.
+    fn at(&self, component: &PlacedComponent, pin: &BoardPin) -> Vec<&PadOutline<'a>> {
+        let owns = |graphic: &BoardGraphic| {
+            if graphic.refdes.is_empty() {
+                graphic.sym_name.is_empty() || graphic.sym_name == component.sym_name
+            } else {
+                graphic.refdes == component.refdes
+            }
+        };
+
+        let x = pin.x_mm.to_f64().unwrap_or(0.0);
+        let y = pin.y_mm.to_f64().unwrap_or(0.0);
+        let (cx, cy) = Self::cell(x, y);
+        (cx - 1..=cx + 1)
+            .flat_map(|i| (cy - 1..=cy + 1).map(move |j| (i, j)))
+            .filter_map(|key| self.grid.get(&key))
+            .flatten()
+            .map(|&index| &self.outlines[index])
+            .filter(|outline| {
+                let (ox, oy) = outline.bounds.center();
+                (ox - x).hypot(oy - y) <= PAD_CENTER_TOLERANCE_MM && owns(outline.graphic)
+            })
+            .collect()
+    }
+}
+
+/// Infers the shape and size of each pin's pad from the pad graphics at its
+/// location.
+///
+/// A pad outline matches a pin if it's on a PIN or PAD class or subclass,
+/// belongs to the pin's component (or to no component), and is centered on
+/// the pin. The smallest matching outline is used, since larger ones are
+/// usually solder mask or paste openings. Pins that match nothing keep a
+/// circular pad with a diameter of twice their radius.
+///
+/// # Arguments
+///
+/// * `components` - The board's components, whose pins are updated.
+/// * `pads` - The board's pad outlines.
+pub(crate) fn infer_pad_shapes(
+    components: &mut BTreeMap<String, PlacedComponent>,
+    pads: &PadIndex,
+) {
+    let to_decimal = |v: f64| Decimal::from_f64_retain(v).unwrap_or_default().round_dp(6);
+    for component in components.values_mut() {
+        let matches: Vec<Option<(PadShape, Rect)>> = component
+            .pins
+            .iter()
+            .map(|pin| {
+                pads.at(component, pin)
+                    .into_iter()
+                    .filter(|outline| !outline.drill)
+                    .min_by(|a, b| a.bounds.area_mm2().total_cmp(&b.bounds.area_mm2()))
+                    .map(|o| (o.shape, o.bounds))
+            })
+            .collect();
+
+        for (pin, best) in component.pins.iter_mut().zip(matches) {
+            if let Some((shape, bounds)) = best {
+                pin.shape = shape;
+                pin.width_mm = to_decimal(bounds.width_mm());
+                pin.height_mm = to_decimal(bounds.height_mm());
+            }
+        }
+    }
+}
+
+/// Replaces missing and implausible pad sizes with ones derived from the
+/// pitch of each component's pins.
+///
+/// A pad's size is implausible if it's zero, or if it's so large along the
+/// line to the nearest pin of the same component that the two pads would
+/// overlap. Such pads become circles half as wide as that pitch, and are
+/// flagged with [BoardPin::size_inferred](super::BoardPin::size_inferred).
+/// Pins without another pin in their component have no pitch, so their sizes
+/// are left alone. The radius from the file is kept as it was.
+///
+/// # Arguments
+///
+/// * `components` - The board's components, whose pins are updated.
+pub(crate) fn infer_pad_sizes(components: &mut BTreeMap<String, PlacedComponent>) {
+    for component in components.values_mut() {
+        let pins = &component.pins;
+        let mut inferred = Vec::new();
+        for (index, pin) in pins.iter().enumerate() {
+            let nearest = pins
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != index)
+                .map(|(_, p)| (p.x_mm - pin.x_mm, p.y_mm - pin.y_mm))
+                .filter(|&(dx, dy)| !dx.is_zero() || !dy.is_zero())
+                .map(|(dx, dy)| {
+                    let dx = dx.to_f64().unwrap_or(0.0);
+                    let dy = dy.to_f64().unwrap_or(0.0);
+                    (dx.hypot(dy), dx, dy)
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0));
+            let Some((pitch, dx, dy)) = nearest else {
+                continue;
+            };
+
+            let width = pin.width_mm.to_f64().unwrap_or(0.0);
+            let height = pin.height_mm.to_f64().unwrap_or(0.0);
+            // The extent of the pad along the line to its neighbor
+            let extent = (width * dx.abs() + height * dy.abs()) / pitch;
+            if width <= 0.0 || height <= 0.0 || extent > pitch {
+                let pitch = Decimal::from_f64_retain(pitch).unwrap_or_default();
+                inferred.push((index, (pitch * INFERRED_PAD_PITCH_RATIO).round_dp(6)));
+            }
+        }
+
+        for (index, size) in inferred {
+            let pin = &mut component.pins[index];
+            pin.shape = PadShape::Circle;
+            pin.width_mm = size;
+            pin.height_mm = size;
+            pin.size_inferred = true;
+        }
+    }
+}
+
+/// Decides which pins are through-hole and estimates their drill diameters.
+///
+/// A pin is through-hole if a drill or hole graphic is centered on it, or if
+/// it has pad graphics on both the top and bottom layers. Every pin of a
+/// component is through-hole if its description names a through-hole
+/// package (e.g., "DIP"), or if it's a connector whose pad graphics are all
+/// round, since surface-mount connectors have rectangular pads. Connectors
+/// with no pad graphics are left alone, since their pins' shapes are only
+/// the circular default. Finally, insertion
+/// codes that are mostly used by through-hole components on the board mark
+/// the rest of the components that use them as through-hole too.
+///
+/// Drill diameters come from the drill graphics where there are any, and
+/// are otherwise estimated from the size of the pad.
+///
+/// # Arguments
+///
+/// * `components` - The board's components, whose pins are updated.
+/// * `pads` - The board's pad and drill outlines.
+pub(crate) fn infer_pad_types(components: &mut BTreeMap<String, PlacedComponent>, pads: &PadIndex) {
+    let to_decimal = |v: f64| Decimal::from_f64_retain(v).unwrap_or_default().round_dp(6);
+
+    // The drill diameter, if known, of each pin with through-hole evidence
+    let mut evidence: BTreeMap<&str, Vec<Option<Option<Decimal>>>> = BTreeMap::new();
+    let mut whole: BTreeMap<&str, bool> = BTreeMap::new();
+    for (refdes, component) in components.iter() {
+        // The shape of the pad graphic each pin matched, as in
+        // infer_pad_shapes
+        let mut shapes = Vec::new();
+        let pins = component
+            .pins
+            .iter()
+            .map(|pin| {
+                let outlines = pads.at(component, pin);
+                shapes.extend(
+                    outlines
+                        .iter()
+                        .filter(|o| !o.drill)
+                        .min_by(|a, b| a.bounds.area_mm2().total_cmp(&b.bounds.area_mm2()))
+                        .map(|o| o.shape),
+                );
+                let drill = outlines
+                    .iter()
+                    .filter(|o| o.drill)
+                    .map(|o| o.bounds.width_mm().min(o.bounds.height_mm()))
+                    .filter(|&d| d > 0.0)
+                    .min_by(f64::total_cmp);
+                let sides: Vec<Option<Side>> = outlines
+                    .iter()
+                    .filter(|o| !o.drill)
+                    .map(|o| o.side())
+                    .collect();
+                let both_sides =
+                    sides.contains(&Some(Side::Top)) && sides.contains(&Some(Side::Bottom));
+                (drill.is_some() || both_sides).then(|| drill.map(to_decimal))
+            })
+            .collect();
+        evidence.insert(refdes, pins);
+
+        let upper = component.description.to_ascii_uppercase();
+        let named = upper
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| THROUGH_HOLE_WORDS.contains(&word));
+        let round_connector = component.kind == ComponentKind::Connector
+            && !shapes.is_empty()
+            && shapes
+                .iter()
+                .all(|shape| matches!(shape, PadShape::Circle | PadShape::Oval));
+        whole.insert(refdes, named || round_connector);
+    }
+
+    // Count the components using each insertion code, and how many of those
+    // have some through-hole evidence
+    let mut codes: BTreeMap<u64, (usize, usize)> = BTreeMap::new();
+    for (refdes, component) in components.iter() {
+        if let Some(code) = component.insertion_code {
+            let counts = codes.entry(code).or_default();
+            counts.0 += 1;
+            if whole[refdes.as_str()] || evidence[refdes.as_str()].iter().any(Option::is_some) {
+                counts.1 += 1;
+            }
+        }
+    }
+    let through_hole_code = |code: Option<u64>| {
+        code.and_then(|code| codes.get(&code))
+            .is_some_and(|&(total, through_hole)| through_hole >= 2 && 2 * through_hole > total)
+    };
+
+    // Decide each pin's type before updating any, in the order of the
+    // components
+    let decisions: Vec<Vec<Option<Option<Decimal>>>> = components
+        .iter()
+        .map(|(refdes, component)| {
+            let all = whole[refdes.as_str()] || through_hole_code(component.insertion_code);
+            evidence[refdes.as_str()]
+                .iter()
+                .map(|pin| match pin {
+                    Some(drill) => Some(*drill),
+                    None if all => Some(None),
+                    None => None,
+                })
+                .collect()
+        })
+        .collect();
+
+    for (component, pins) in components.values_mut().zip(decisions) {
+        for (pin, decision) in component.pins.iter_mut().zip(pins) {
+            let Some(drill) = decision else {
+                continue;
+            };
+            pin.pad_type = PadType::ThroughHole;
+            pin.drill_mm = drill.or_else(|| {
+                let size = pin.width_mm.min(pin.height_mm);
+                (size > Decimal::ZERO).then(|| (size * ESTIMATED_DRILL_PAD_RATIO).round_dp(2))
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::{board_from, board_with_description};
+
+    #[test]
+    fn test_infer_pad_shapes() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!U1!1!QFN!NO!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!U1!1!1!0!0!!0.2!\n\
+S!B!U1!2!2!2!0!!0.2!\n\
+S!C!U1!3!3!4!0!!0.2!\n\
+S!D!U1!4!4!6!0!!0.2!\n\
+A!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!GRAPHIC_DATA_2!\
+GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!GRAPHIC_DATA_8!\
+GRAPHIC_DATA_9!SUBCLASS!SYM_NAME!REFDES!\n\
+S!RECTANGLE!1!1 1 0!-0.3!-0.5!0.3!0.5!1!!!!!PAD_TOP!QFN!U1!\n\
+S!RECTANGLE!1!1 2 0!-0.4!-0.6!0.4!0.6!1!!!!!SOLDERMASK_TOP!QFN!U1!\n\
+S!OBLONG_X!2!2 1 0!2!0!1!0.5!!!!!!PAD_TOP!QFN!U1!\n\
+S!LINE!3!3 1 0!3.8!-0.5!4.2!-0.5!0!!!!!PAD_TOP!QFN!U1!\n\
+S!ARC!3!3 2 0!4.2!-0.5!4.3!-0.4!4.2!-0.4!0.1!0!COUNTERCLOCKWISE!PAD_TOP!QFN!U1!\n\
+S!LINE!3!3 3 0!4.3!-0.4!4.3!0.4!0!!!!!PAD_TOP!QFN!U1!\n\
+S!ARC!3!3 4 0!4.3!0.4!4.2!0.5!4.2!0.4!0.1!0!COUNTERCLOCKWISE!PAD_TOP!QFN!U1!\n\
+S!LINE!3!3 5 0!4.2!0.5!3.8!0.5!0!!!!!PAD_TOP!QFN!U1!\n\
+S!ARC!3!3 6 0!3.8!0.5!3.7!0.4!3.8!0.4!0.1!0!COUNTERCLOCKWISE!PAD_TOP!QFN!U1!\n\
+S!LINE!3!3 7 0!3.7!0.4!3.7!-0.4!0!!!!!PAD_TOP!QFN!U1!\n\
+S!ARC!3!3 8 0!3.7!-0.4!3.8!-0.5!3.8!-0.4!0.1!0!COUNTERCLOCKWISE!PAD_TOP!QFN!U1!\n";
+        let board = board_from(content);
+        let pins = &board.components["U1"].pins;
+
+        // The copper pad is used instead of the larger mask opening
+        assert_eq!(pins[0].shape, PadShape::Rect);
+        assert_eq!(pins[0].width_mm, Decimal::new(6, 1));
+        assert_eq!(pins[0].height_mm, Decimal::ONE);
+        assert_eq!(pins[1].shape, PadShape::Oval);
+        assert_eq!(pins[2].shape, PadShape::RoundRect);
+        assert_eq!(pins[2].width_mm, Decimal::new(6, 1));
+
+        // Nothing matches, so the pad stays a circle
+        assert_eq!(pins[3].shape, PadShape::Circle);
+        assert_eq!(pins[3].width_mm, Decimal::new(4, 1));
+        assert!(!pins[3].size_inferred);
+    }
+
+    #[test]
+    fn test_infer_pad_sizes() {
+        let content = b"A!UNIT!millimeters!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!J1!1!1!0!0!!0!\n\
+S!B!J1!2!2!1!0!!0.3!\n\
+S!C!J1!3!3!2!0!!5!\n\
+S!D!TP1!1!1!9!9!!0!\n";
+        let board = board_from(content);
+        let pins = &board.components["J1"].pins;
+
+        let sizes: Vec<_> = pins.iter().map(|p| (p.width_mm, p.size_inferred)).collect();
+        assert_eq!(
+            sizes,
+            [
+                (Decimal::new(5, 1), true),
+                (Decimal::new(6, 1), false),
+                (Decimal::new(5, 1), true),
+            ]
+        );
+        assert_eq!(pins[2].radius_mm, Decimal::new(5, 0));
+
+        // A lone pin has no pitch to go by
+        assert!(!board.components["TP1"].pins[0].size_inferred);
+    }
+
+    #[test]
+    fn test_infer_pad_types() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!J1!1!HDR!NO!0!\n\
+S!J2!1!USB!NO!0!\n\
+S!J3!1!FPC!NO!0!\n\
+S!U1!1!TO220!NO!0!\n\
+S!R1!1!R0402!NO!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!J1!1!1!0!0!!0.85!\n\
+S!B!J1!2!2!2.54!0!!0.85!\n\
+S!A!J2!1!1!0!5!!0.2!\n\
+S!B!J2!2!2!1!5!!0.2!\n\
+S!A!J3!1!1!0!20!!0.2!\n\
+S!B!J3!2!2!1!20!!0.2!\n\
+S!A!U1!1!1!0!10!!1!\n\
+S!B!U1!2!2!2.54!10!!1!\n\
+S!A!R1!1!1!0!15!!0.25!\n\
+S!B!R1!2!2!1!15!!0.25!\n\
+A!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!GRAPHIC_DATA_2!\
+GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!GRAPHIC_DATA_8!\
+GRAPHIC_DATA_9!SUBCLASS!SYM_NAME!REFDES!\n\
+S!CIRCLE!5!5 1 0!0!0!1.7!!!!!!!PAD_TOP!HDR!J1!\n\
+S!CIRCLE!5!5 2 0!2.54!0!1.7!!!!!!!PAD_TOP!HDR!J1!\n\
+S!RECTANGLE!1!1 1 0!-0.2!4.8!0.2!5.2!1!!!!!PAD_TOP!USB!J2!\n\
+S!RECTANGLE!2!2 1 0!0.8!4.8!1.2!5.2!1!!!!!PAD_TOP!USB!J2!\n\
+S!CIRCLE!3!3 1 0!0!10!2!!!!!!!PAD_TOP!TO220!U1!\n\
+S!CIRCLE!3!3 2 0!0!10!2!!!!!!!PAD_BOTTOM!TO220!U1!\n\
+S!CIRCLE!4!4 1 0!2.54!10!1.1!!!!!!!DRILL!TO220!U1!\n";
+        let description = b"B|1|B|1|PN\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\n\
+1\tCONN HEADER 2P 2.54MM\t1\tJ1\t\n\
+2\tCONN USB TYPE-C\t1\tJ2\t\n\
+3\tCONN FPC 2P\t1\tJ3\t\n";
+        let board = board_with_description(content, description);
+        let types = |refdes: &str| -> Vec<_> {
+            board.components[refdes]
+                .pins
+                .iter()
+                .map(|p| (p.pad_type, p.drill_mm))
+                .collect()
+        };
+
+        // Round connector pads get a drill estimated from the pad size, but a
+        // connector with no pad graphics only has the default circles
+        let drill = Some(Decimal::new(102, 2));
+        assert_eq!(
+            types("J1"),
+            [(PadType::ThroughHole, drill), (PadType::ThroughHole, drill)]
+        );
+        assert_eq!(types("J2"), [(PadType::Smd, None), (PadType::Smd, None)]);
+        assert_eq!(types("J3"), [(PadType::Smd, None), (PadType::Smd, None)]);
+
+        // One pin has pads on both sides, and the other has a drill graphic
+        let u1 = types("U1");
+        assert_eq!(u1[0], (PadType::ThroughHole, Some(Decimal::new(12, 1))));
+        assert_eq!(u1[1], (PadType::ThroughHole, Some(Decimal::new(11, 1))));
+        assert_eq!(types("R1")[0].0, PadType::Smd);
+    }
+}
@@ -0,0 +1,418 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/power.rs - Power-rail tree for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use rust_decimal::Decimal;
+
+use super::Board;
+use super::ComponentKind;
+use super::ConnectivityGraph;
+use super::Net;
+use super::Netlist;
+use super::PowerState;
+use super::classify::refdes_prefix;
+use super::netinfo::is_word_separator;
+use super::netinfo::words;
+
+/// A power rail in a [PowerTree].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerRail {
+    /// The name of the rail's net.
+    pub name: String,
+    /// The nominal voltage of the rail, if it can be inferred from its name.
+    pub voltage: Option<Decimal>,
+    /// The power state the rail is powered in, if its name says.
+    pub power_state: Option<PowerState>,
+    /// The name of the rail this rail is derived from, if known.
+    pub parent: Option<String>,
+    /// The reference designator of the component that derives this rail from
+    /// its parent (e.g., a regulator, load switch, or ferrite bead).
+    pub converter: Option<String>,
+    /// The number of components on the rail, not counting the components
+    /// that connect it to other rails.
+    pub load_count: usize,
+}
+
+/// The power rails of a board, arranged by which rail feeds which.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerTree {
+    /// The rails on the board, by name.
+    pub rails: BTreeMap<String, PowerRail>,
+}
+
+/// Returns whether a net looks like a power rail rather than a signal.
+fn is_rail(net: &Net) -> bool {
+    const SIGNAL_WORDS: &[&str] = &[
+        "EN", "PG", "PGOOD", "PWRGD", "PWROK", "OK", "SNS", "SENSE", "FB", "SW", "LX", "PHASE",
+        "BOOT", "BST", "UGATE", "LGATE", "GATE", "DRV", "CTRL", "ON", "OFF", "DET", "R", "L",
+    ];
+    const RAIL_PREFIXES: &[&str] = &[
+        "VCC", "VDD", "VIN", "VBAT", "VBUS", "VCORE", "VTT", "VPP", "PP",
+    ];
+
+    let upper = net.name.to_ascii_uppercase();
+    if net.no_connect || net.info.ground || upper.is_empty() {
+        return false;
+    }
+    if words(&upper, is_word_separator).any(|word| SIGNAL_WORDS.contains(&word)) {
+        return false;
+    }
+
+    upper.starts_with('+')
+        || net.info.voltage.is_some()
+        || RAIL_PREFIXES.iter().any(|p| upper.starts_with(p))
+}
+
+/// Orders two rails so that the one more likely to feed the other compares
+/// greater.
+fn upstream_order(a: &PowerRail, b: &PowerRail) -> Ordering {
+    let by_voltage = match (a.voltage, b.voltage) {
+        (Some(va), Some(vb)) => va.cmp(&vb),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    };
+    by_voltage
+        .then_with(|| a.power_state.cmp(&b.power_state))
+        .then_with(|| {
+            b.name
+                .starts_with(&a.name)
+                .cmp(&a.name.starts_with(&b.name))
+        })
+        .then_with(|| b.name.len().cmp(&a.name.len()))
+}
+
+/// The role a component can play in converting power between rails.
+#[derive(PartialEq)]
+enum PowerRole {
+    /// A regulator, converter controller, or load switch IC.
+    Regulator,
+    /// A discrete MOSFET.
+    Mosfet,
+}
+
+/// Returns whether `text_words` contains the words of `phrase` in a row.
+fn has_phrase(text_words: &[&str], phrase: &str) -> bool {
+    let phrase: Vec<&str> = words(phrase, is_word_separator).collect();
+    text_words.windows(phrase.len()).any(|w| w == phrase)
+}
+
+/// Classifies a component's power role from its BOM description, or from its
+/// reference designator prefix if the description doesn't say.
+///
+/// Keywords are matched as whole words, ignoring punctuation, so that "REG"
+/// doesn't match "REGISTER".
+fn power_role(refdes: &str, description: &str) -> Option<PowerRole> {
+    const REGULATOR_WORDS: &[&str] = &[
+        "LDO",
+        "REG",
+        "REGULATOR",
+        "VREG",
+        "BUCK",
+        "BOOST",
+        "PWM",
+        "DC-DC",
+        "CONVERTER",
+        "LOAD SW",
+        "LOAD SWITCH",
+        "POWER SW",
+        "POWER SWITCH",
+    ];
+    const MOSFET_WORDS: &[&str] = &["MOSFET", "FET", "N-CH", "P-CH", "NMOS", "PMOS"];
+
+    let upper = description.to_ascii_uppercase();
+    let description_words: Vec<&str> = words(&upper, is_word_separator).collect();
+    if REGULATOR_WORDS
+        .iter()
+        .any(|w| has_phrase(&description_words, w))
+    {
+        return Some(PowerRole::Regulator);
+    }
+    if MOSFET_WORDS
+        .iter()
+        .any(|w| has_phrase(&description_words, w))
+    {
+        return Some(PowerRole::Mosfet);
+    }
+    if !upper.is_empty() {
+        return None;
+    }
+
+    match refdes_prefix(refdes).as_str() {
+        "PU" => Some(PowerRole::Regulator),
+        "Q" | "PQ" => Some(PowerRole::Mosfet),
+        _ => None,
+    }
+}
+
+impl PowerTree {
+    /// Infers the power tree of a board.
+    ///
+    /// Rails are found by net name. A rail's parent is inferred, in order of
+    /// preference, from a regulator whose switch node drives the rail through
+    /// an inductor, from a regulator, load switch, or MOSFET with pins on
+    /// both rails, or from a ferrite bead, inductor, jumper, or fuse that
+    /// connects the two rails directly. The parent is the higher-voltage
+    /// rail, or the rail with the more always-on power state if the voltages
+    /// are the same.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    /// * `netlist` - The board's netlist.
+    pub fn from_board(board: &Board, netlist: &Netlist) -> Self {
+        let mut rails: BTreeMap<String, PowerRail> = netlist
+            .nets
+            .values()
+            .filter(|net| is_rail(net))
+            .map(|net| {
+                (
+                    net.name.clone(),
+                    PowerRail {
+                        name: net.name.clone(),
+                        voltage: net.info.voltage,
+                        power_state: net.info.power_state,
+                        parent: None,
+                        converter: None,
+                        load_count: 0,
+                    },
+                )
+            })
+            .collect();
+
+        let roles: BTreeMap<&str, PowerRole> = board
+            .components
+            .iter()
+            .filter_map(|(refdes, c)| {
+                power_role(refdes, &c.description).map(|role| (refdes.as_str(), role))
+            })
+            .collect();
+        let rails_of = |refdes: &str| -> Vec<&str> {
+            netlist
+                .component_nets(refdes)
+                .into_iter()
+                .filter(|name| rails.contains_key(*name))
+                .collect()
+        };
+        let best_parent = |candidates: &[&'_ str], child: &str| -> Option<String> {
+            candidates
+                .iter()
+                .filter(|name| **name != child)
+                .map(|name| &rails[*name])
+                .filter(|rail| upstream_order(rail, &rails[child]) == Ordering::Greater)
+                .max_by(|a, b| upstream_order(a, b))
+                .map(|rail| rail.name.clone())
+        };
+
+        let graph = ConnectivityGraph::new(board, netlist);
+        let mut links: Vec<(String, String, String, Vec<String>)> = Vec::new();
+
+        // Regulators driving a rail through an inductor from a switch node
+        for element in &graph.elements {
+            if element.kind != ComponentKind::Inductor {
+                continue;
+            }
+            let (rail, node) = match (
+                rails.contains_key(&element.nets[0]),
+                rails.contains_key(&element.nets[1]),
+            ) {
+                (true, false) => (&element.nets[0], &element.nets[1]),
+                (false, true) => (&element.nets[1], &element.nets[0]),
+                _ => continue,
+            };
+
+            let drivers: Vec<&str> = netlist
+                .net(node)
+                .into_iter()
+                .flat_map(|net| net.pins.iter().map(|p| p.refdes.as_str()))
+                .filter(|refdes| roles.contains_key(refdes))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+            let candidates: Vec<&str> = drivers.iter().flat_map(|r| rails_of(r)).collect();
+            let Some(parent) = best_parent(&candidates, rail) else {
+                continue;
+            };
+            let converter = drivers
+                .iter()
+                .find(|r| roles[**r] == PowerRole::Regulator)
+                .or_else(|| {
+                    drivers
+                        .iter()
+                        .find(|r| rails_of(r).contains(&parent.as_str()))
+                })
+                .unwrap_or(&drivers[0]);
+
+            let mut bridges: Vec<String> = drivers.iter().map(|r| r.to_string()).collect();
+            bridges.push(element.refdes.clone());
+            links.push((parent, rail.clone(), converter.to_string(), bridges));
+        }
+
+        // Regulators, load switches, and MOSFETs with pins on several rails
+        for refdes in roles.keys() {
+            let touched = rails_of(refdes);
+            for child in &touched {
+                if let Some(parent) = best_parent(&touched, child) {
+                    links.push((
+                        parent,
+                        child.to_string(),
+                        refdes.to_string(),
+                        vec![refdes.to_string()],
+                    ));
+                }
+            }
+        }
+
+        // Rails connected directly through series components
+        for element in &graph.elements {
+            if element.kind == ComponentKind::Resistor {
+                continue;
+            }
+            let [a, b] = &element.nets;
+            if !rails.contains_key(a) || !rails.contains_key(b) {
+                continue;
+            }
+            let (parent, child) = match upstream_order(&rails[a], &rails[b]) {
+                Ordering::Less => (b, a),
+                _ => (a, b),
+            };
+            links.push((
+                parent.clone(),
+                child.clone(),
+                element.refdes.clone(),
+                vec![element.refdes.clone()],
+            ));
+        }
+
+        let mut bridges: BTreeSet<String> = BTreeSet::new();
+        for (parent, child, converter, link_bridges) in links {
+            if rails[&child].parent.is_some() || is_ancestor(&rails, &child, &parent) {
+                continue;
+            }
+            let rail = rails.get_mut(&child).unwrap();
+            rail.parent = Some(parent);
+            rail.converter = Some(converter);
+            bridges.extend(link_bridges);
+        }
+
+        for rail in rails.values_mut() {
+            let loads: BTreeSet<&str> = netlist
+                .net(&rail.name)
+                .into_iter()
+                .flat_map(|net| net.pins.iter().map(|p| p.refdes.as_str()))
+                .filter(|refdes| !bridges.contains(*refdes))
+                .collect();
+            rail.load_count = loads.len();
+        }
+
+        Self { rails }
+    }
+
+    /// Returns the rails that have no parent, in name order.
+    pub fn roots(&self) -> Vec<&PowerRail> {
+        self.rails.values().filter(|r| r.parent.is_none()).collect()
+    }
+
+    /// Returns the rails derived directly from a rail, in name order.
+    pub fn children(&self, name: &str) -> Vec<&PowerRail> {
+        self.rails
+            .values()
+            .filter(|r| r.parent.as_deref() == Some(name))
+            .collect()
+    }
+}
+
+/// Returns whether `ancestor` is `name` or one of its ancestors.
+fn is_ancestor(rails: &BTreeMap<String, PowerRail>, ancestor: &str, name: &str) -> bool {
+    let mut current = Some(name);
+    while let Some(rail) = current {
+        if rail == ancestor {
+            return true;
+        }
+        current = rails.get(rail).and_then(|r| r.parent.as_deref());
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_with_description;
+
+    #[test]
+    fn test_power_tree() {
+        let content = b"A!UNIT!millimeters!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!+19V!PU1!1!VIN!0!0!!0.25!\n\
+S!PHASE1!PU1!2!LX!1!0!!0.25!\n\
+S!GND!PU1!3!GND!2!0!!0.25!\n\
+S!PHASE1!PL1!1!1!0!2!!0.25!\n\
+S!+5VALW!PL1!2!2!1!2!!0.25!\n\
+S!+5VALW!U2!1!IN!0!4!!0.25!\n\
+S!+5VS!U2!2!OUT!1!4!!0.25!\n\
+S!SUSP#!U2!3!EN!2!4!!0.25!\n\
+S!+5VS!FB1!1!1!0!6!!0.25!\n\
+S!+5VS_USB!FB1!2!2!1!6!!0.25!\n\
+S!+5VS!C1!1!1!0!8!!0.25!\n\
+S!GND!C1!2!2!1!8!!0.25!\n\
+S!+5VS_USB!J1!1!VBUS!0!10!!0.25!\n\
+S!GND!J1!2!GND!1!10!!0.25!\n";
+        let description = b"BOARD|1.00|BOARD|1.00|PN\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\n\
+1\tIC BUCK CONVERTER\t1\tPU1\t\n\
+2\tIC LOAD SWITCH\t1\tU2\t\n\
+3\tBEAD 120OHM\t1\tFB1\t\n";
+        let board = board_with_description(content, description);
+        let tree = PowerTree::from_board(&board, &Netlist::from_board(&board));
+
+        assert_eq!(
+            tree.rails.keys().collect::<Vec<_>>(),
+            ["+19V", "+5VALW", "+5VS", "+5VS_USB"]
+        );
+        let rail = &tree.rails["+5VALW"];
+        assert_eq!(rail.parent.as_deref(), Some("+19V"));
+        assert_eq!(rail.converter.as_deref(), Some("PU1"));
+        assert_eq!(tree.rails["+5VS"].parent.as_deref(), Some("+5VALW"));
+        assert_eq!(tree.rails["+5VS"].converter.as_deref(), Some("U2"));
+        assert_eq!(tree.rails["+5VS"].load_count, 1);
+        assert_eq!(tree.rails["+5VS_USB"].parent.as_deref(), Some("+5VS"));
+        assert_eq!(tree.rails["+5VS_USB"].converter.as_deref(), Some("FB1"));
+        assert_eq!(tree.roots().len(), 1);
+        assert_eq!(tree.children("+5VS").len(), 1);
+    }
+
+    #[test]
+    fn test_power_role() {
+        let role = |description| power_role("U1", description);
+        assert!(role("IC LDO 3.3V SOT23") == Some(PowerRole::Regulator));
+        assert!(role("IC DC/DC BUCK") == Some(PowerRole::Regulator));
+        assert!(role("IC POWER SWITCH") == Some(PowerRole::Regulator));
+        assert!(role("MOSFET N-CH 30V") == Some(PowerRole::Mosfet));
+        assert!(role("TRANS P-CH 20V") == Some(PowerRole::Mosfet));
+        assert!(role("IC SHIFT REGISTER").is_none());
+        assert!(role("IC FETCH BUFFER").is_none());
+        assert!(power_role("PU1", "") == Some(PowerRole::Regulator));
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/shapes.rs - Closed shape reconstruction for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use super::Board;
+use super::BoardGraphic;
+use super::Polygon;
+use super::Primitive;
+use super::chain_polygons;
+use super::traces::COPPER_TOLERANCE_MM;
+
+/// A filled shape, such as a copper pour, with any holes cut out of it.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shape {
+    /// The class of the shape's records (e.g., "ETCH").
+    pub class: String,
+    /// The layer (subclass) the shape is on.
+    pub layer: String,
+    /// The name of the net the shape is on, or an empty string.
+    pub net_name: String,
+    /// The graphic data number shared by the shape's records.
+    pub number: u64,
+    /// The outer edge of the shape.
+    pub outline: Polygon,
+    /// The holes in the shape.
+    pub holes: Vec<Polygon>,
+    /// The area of the shape, not counting its holes, in square millimeters.
+    pub area_mm2: f64,
+}
+
+impl Shape {
+    /// Returns whether a point is inside the shape and not in one of its
+    /// holes.
+    pub fn contains(&self, x_mm: f64, y_mm: f64) -> bool {
+        self.outline.contains(x_mm, y_mm) && !self.holes.iter().any(|h| h.contains(x_mm, y_mm))
+    }
+}
+
+/// The filled shapes of a board.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shapes {
+    /// The shapes, in the order their records first appear.
+    pub shapes: Vec<Shape>,
+}
+
+/// Returns whether a polygon is inside another, judged by its first point.
+fn inside(inner: &Polygon, outer: &Polygon) -> bool {
+    inner
+        .points
+        .first()
+        .is_some_and(|&(x, y)| outer.contains(x, y))
+}
+
+/// Returns whether a graphic is a classed record that can outline a shape.
+fn is_shape_edge(graphic: &BoardGraphic) -> bool {
+    !graphic.class.is_empty()
+        && matches!(
+            graphic.primitive,
+            Primitive::Line { .. }
+                | Primitive::Arc { .. }
+                | Primitive::Circle { .. }
+                | Primitive::Rectangle { .. }
+        )
+}
+
+impl Shapes {
+    /// Assembles the filled shapes of a board from its classed graphics.
+    ///
+    /// The line and arc segments of each figure are chained into closed
+    /// polygons, and rectangles and circles are polygons of their own. Within
+    /// a figure, a polygon inside an odd number of larger polygons is a hole
+    /// in the smallest of them, and any other polygon is the outline of a
+    /// separate shape. Figures with no closed polygons have no shapes.
+    ///
+    /// Every class is included, so the board outline and other closed
+    /// figures appear alongside the copper pours. Filter by
+    /// [Shape::class] to get only the ETCH shapes.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    pub fn from_board(board: &Board) -> Self {
+        let mut shapes = Vec::new();
+
+        for figure in board.figures(is_shape_edge) {
+            let mut polygons =
+                chain_polygons(figure.iter().map(|g| &g.primitive), COPPER_TOLERANCE_MM);
+            polygons.sort_by(|a, b| b.area_mm2().total_cmp(&a.area_mm2()));
+
+            let first = shapes.len();
+            for polygon in polygons {
+                let containers = shapes[first..]
+                    .iter()
+                    .filter(|s: &&Shape| inside(&polygon, &s.outline))
+                    .count()
+                    + shapes[first..]
+                        .iter()
+                        .flat_map(|s: &Shape| &s.holes)
+                        .filter(|h| inside(&polygon, h))
+                        .count();
+
+                if containers % 2 == 1 {
+                    // The smallest containing shape is the last one added
+                    let owner = shapes[first..]
+                        .iter_mut()
+                        .rev()
+                        .find(|s: &&mut Shape| inside(&polygon, &s.outline));
+                    if let Some(owner) = owner {
+                        owner.area_mm2 -= polygon.area_mm2();
+                        owner.holes.push(polygon);
+                        continue;
+                    }
+                }
+
+                let graphic = figure[0];
+                shapes.push(Shape {
+                    class: graphic.class.clone(),
+                    layer: graphic.subclass.clone(),
+                    net_name: graphic.net_name.clone(),
+                    number: graphic.number,
+                    area_mm2: polygon.area_mm2(),
+                    outline: polygon,
+                    holes: Vec::new(),
+                });
+            }
+        }
+
+        Self { shapes }
+    }
+
+    /// Returns the shapes on a net.
+    pub fn net(&self, name: &str) -> Vec<&Shape> {
+        self.shapes.iter().filter(|s| s.net_name == name).collect()
+    }
+
+    /// Returns the shapes on a layer.
+    pub fn layer(&self, name: &str) -> Vec<&Shape> {
+        self.shapes
+            .iter()
+            .filter(|s| s.layer.eq_ignore_ascii_case(name))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_shapes() {
+        let content = b"A!UNIT!millimeters!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!ETCH!GND02!LINE!7!1 1 0!0!0!20!0!0!!!!!GND!\n\
+S!ETCH!GND02!LINE!7!2 1 0!20!0!20!20!0!!!!!GND!\n\
+S!ETCH!GND02!LINE!7!3 1 0!20!20!0!20!0!!!!!GND!\n\
+S!ETCH!GND02!LINE!7!4 1 0!0!20!0!0!0!!!!!GND!\n\
+S!ETCH!GND02!RECTANGLE!7!5 1 0!5!5!15!15!0!!!!!GND!\n\
+S!ETCH!GND02!RECTANGLE!7!6 1 0!8!8!12!12!1!!!!!GND!\n\
+S!ETCH!TOP!LINE!8!1 1 0!0!0!5!0!0.1!!!!!CLK!\n";
+        let board = board_from(content);
+        let shapes = Shapes::from_board(&board);
+
+        assert_eq!(shapes.shapes.len(), 2);
+        let pour = &shapes.net("GND")[0];
+        assert_eq!(pour.layer, "GND02");
+        assert_eq!(pour.holes.len(), 1);
+        assert!((pour.area_mm2 - 300.0).abs() < 1e-9);
+        assert!(pour.contains(2.0, 2.0));
+        assert!(!pour.contains(6.0, 6.0));
+        assert!((shapes.net("GND")[1].area_mm2 - 16.0).abs() < 1e-9);
+        assert!(shapes.net("CLK").is_empty());
+    }
+}
@@ -0,0 +1,378 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/spatial.rs - Spatial index for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use super::Board;
+use super::PadType;
+use super::Side;
+
+/// The kind of item in a [SpatialIndex].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpatialKind {
+    /// A component pin.
+    Pin,
+    /// A via.
+    Via,
+    /// A test via.
+    TestVia,
+}
+
+/// A pin, via, or test via in a [SpatialIndex].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpatialItem {
+    /// The kind of item.
+    pub kind: SpatialKind,
+    /// The reference designator of the item's component, or an empty string
+    /// if it has none.
+    pub refdes: String,
+    /// The number of the pin, or an empty string if the item isn't a pin or
+    /// a test via on a pin.
+    pub pin: String,
+    /// The name of the via or test via, or the name of the pin.
+    pub name: String,
+    /// The name of the net the item is connected to.
+    pub net_name: String,
+    /// The side of the board the item is on, or `None` if it's on both.
+    pub side: Option<Side>,
+    /// The X-coordinate of the item's center, in millimeters.
+    pub x_mm: f64,
+    /// The Y-coordinate of the item's center, in millimeters.
+    pub y_mm: f64,
+    /// The radius of the item in millimeters.
+    pub radius_mm: f64,
+}
+
+impl SpatialItem {
+    fn on_side(&self, side: Option<Side>) -> bool {
+        match (side, self.side) {
+            (Some(wanted), Some(actual)) => wanted == actual,
+            _ => true,
+        }
+    }
+
+    fn distance(&self, x: f64, y: f64) -> f64 {
+        (self.x_mm - x).hypot(self.y_mm - y)
+    }
+}
+
+/// A uniform grid over the pins, vias, and test vias of a board, for finding
+/// items by location.
+///
+/// Every query takes an optional side. Items on the other side are skipped,
+/// while items on both sides (through-hole pins, vias, and test vias) always
+/// match.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpatialIndex {
+    /// The indexed items.
+    pub items: Vec<SpatialItem>,
+    min_x: f64,
+    min_y: f64,
+    cell_size: f64,
+    columns: usize,
+    rows: usize,
+    cells: Vec<Vec<usize>>,
+    max_radius: f64,
+}
+
+fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
+impl SpatialIndex {
+    /// Builds the spatial index of a board.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    pub fn from_board(board: &Board) -> Self {
+        let mut items = Vec::new();
+
+        for (refdes, component) in &board.components {
+            for pin in &component.pins {
+                items.push(SpatialItem {
+                    kind: SpatialKind::Pin,
+                    refdes: refdes.clone(),
+                    pin: pin.number.clone(),
+                    name: pin.name.clone(),
+                    net_name: pin.net_name.clone(),
+                    side: match pin.pad_type {
+                        PadType::ThroughHole => None,
+                        PadType::Smd => Some(component.side),
+                    },
+                    x_mm: to_f64(pin.x_mm),
+                    y_mm: to_f64(pin.y_mm),
+                    radius_mm: to_f64(pin.radius_mm),
+                });
+            }
+        }
+        for via in &board.vias {
+            items.push(SpatialItem {
+                kind: SpatialKind::Via,
+                refdes: String::new(),
+                pin: String::new(),
+                name: via.id.clone(),
+                net_name: via.net_name.clone(),
+                side: None,
+                x_mm: to_f64(via.x_mm),
+                y_mm: to_f64(via.y_mm),
+                radius_mm: via.radius_mm.map_or(0.0, to_f64),
+            });
+        }
+        for testvia in &board.testvias {
+            items.push(SpatialItem {
+                kind: SpatialKind::TestVia,
+                refdes: testvia.refdes.clone(),
+                pin: testvia.pin_number.clone(),
+                name: testvia.name.clone(),
+                net_name: testvia.net_name.clone(),
+                side: None,
+                x_mm: to_f64(testvia.x_mm),
+                y_mm: to_f64(testvia.y_mm),
+                radius_mm: to_f64(testvia.radius_mm),
+            });
+        }
+
+        Self::from_items(items)
+    }
+
+    fn from_items(items: Vec<SpatialItem>) -> Self {
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        let mut max_radius: f64 = 0.0;
+        for item in &items {
+            min_x = min_x.min(item.x_mm);
+            min_y = min_y.min(item.y_mm);
+            max_x = max_x.max(item.x_mm);
+            max_y = max_y.max(item.y_mm);
+            max_radius = max_radius.max(item.radius_mm);
+        }
+        if items.is_empty() {
+            (min_x, min_y, max_x, max_y) = (0.0, 0.0, 0.0, 0.0);
+        }
+
+        // Aim for a few items per cell on average
+        let width = (max_x - min_x).max(1.0);
+        let height = (max_y - min_y).max(1.0);
+        let cell_size = (width * height * 4.0 / items.len().max(1) as f64)
+            .sqrt()
+            .max(0.1);
+        let columns = (width / cell_size) as usize + 1;
+        let rows = (height / cell_size) as usize + 1;
+
+        let mut index = Self {
+            items,
+            min_x,
+            min_y,
+            cell_size,
+            columns,
+            rows,
+            cells: vec![Vec::new(); columns * rows],
+            max_radius,
+        };
+        for (i, item) in index.items.iter().enumerate() {
+            let (column, row) = index.cell_of(item.x_mm, item.y_mm);
+            index.cells[row * index.columns + column].push(i);
+        }
+        index
+    }
+
+    /// Returns the cell containing a point, clamped to the grid.
+    fn cell_of(&self, x: f64, y: f64) -> (usize, usize) {
+        let clamp = |value: f64, len: usize| (value.max(0.0) as usize).min(len - 1);
+        (
+            clamp((x - self.min_x) / self.cell_size, self.columns),
+            clamp((y - self.min_y) / self.cell_size, self.rows),
+        )
+    }
+
+    /// Returns the indices of the items whose centers are in the cells that
+    /// overlap a rectangle.
+    fn candidates(&self, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Vec<usize> {
+        let (first_column, first_row) = self.cell_of(min_x, min_y);
+        let (last_column, last_row) = self.cell_of(max_x, max_y);
+        let mut found = Vec::new();
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                found.extend(&self.cells[row * self.columns + column]);
+            }
+        }
+        found
+    }
+
+    /// Returns the items under a point, i.e. those whose radius reaches it.
+    ///
+    /// # Arguments
+    ///
+    /// * `x_mm` - The X-coordinate of the point, in millimeters.
+    /// * `y_mm` - The Y-coordinate of the point, in millimeters.
+    /// * `side` - The side of the board to look at, or `None` for both.
+    pub fn at(&self, x_mm: f64, y_mm: f64, side: Option<Side>) -> Vec<&SpatialItem> {
+        let r = self.max_radius;
+        let mut found: Vec<&SpatialItem> = self
+            .candidates(x_mm - r, y_mm - r, x_mm + r, y_mm + r)
+            .into_iter()
+            .map(|i| &self.items[i])
+            .filter(|item| item.on_side(side) && item.distance(x_mm, y_mm) <= item.radius_mm)
+            .collect();
+        found.sort_by(|a, b| a.distance(x_mm, y_mm).total_cmp(&b.distance(x_mm, y_mm)));
+        found
+    }
+
+    /// Returns up to `k` items nearest to a point, closest first, along with
+    /// the distance from the point to each item's center in millimeters.
+    ///
+    /// # Arguments
+    ///
+    /// * `x_mm` - The X-coordinate of the point, in millimeters.
+    /// * `y_mm` - The Y-coordinate of the point, in millimeters.
+    /// * `k` - The maximum number of items to return.
+    /// * `side` - The side of the board to look at, or `None` for both.
+    pub fn nearest(
+        &self,
+        x_mm: f64,
+        y_mm: f64,
+        k: usize,
+        side: Option<Side>,
+    ) -> Vec<(&SpatialItem, f64)> {
+        let mut found: Vec<(&SpatialItem, f64)> = Vec::new();
+        if k == 0 {
+            return found;
+        }
+
+        let (column, row) = self.cell_of(x_mm, y_mm);
+        let (column, row) = (column as isize, row as isize);
+        let max_ring = self.columns.max(self.rows) as isize;
+        for ring in 0..=max_ring {
+            for dy in -ring..=ring {
+                for dx in -ring..=ring {
+                    if dx.abs() != ring && dy.abs() != ring {
+                        continue;
+                    }
+                    let (c, r) = (column + dx, row + dy);
+                    if c < 0 || r < 0 || c >= self.columns as isize || r >= self.rows as isize {
+                        continue;
+                    }
+                    for &i in &self.cells[r as usize * self.columns + c as usize] {
+                        let item = &self.items[i];
+                        if item.on_side(side) {
+                            found.push((item, item.distance(x_mm, y_mm)));
+                        }
+                    }
+                }
+            }
+
+            // Anything in a further ring is at least this far away
+            found.sort_by(|a, b| a.1.total_cmp(&b.1));
+            let reach = ring as f64 * self.cell_size;
+            if found.len() >= k && found[k - 1].1 <= reach {
+                break;
+            }
+        }
+
+        found.truncate(k);
+        found
+    }
+
+    /// Returns the items whose centers are inside a rectangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_x_mm`, `min_y_mm` - One corner of the rectangle, in millimeters.
+    /// * `max_x_mm`, `max_y_mm` - The opposite corner, in millimeters.
+    /// * `side` - The side of the board to look at, or `None` for both.
+    pub fn in_rect(
+        &self,
+        min_x_mm: f64,
+        min_y_mm: f64,
+        max_x_mm: f64,
+        max_y_mm: f64,
+        side: Option<Side>,
+    ) -> Vec<&SpatialItem> {
+        let (min_x, max_x) = (min_x_mm.min(max_x_mm), min_x_mm.max(max_x_mm));
+        let (min_y, max_y) = (min_y_mm.min(max_y_mm), min_y_mm.max(max_y_mm));
+        self.candidates(min_x, min_y, max_x, max_y)
+            .into_iter()
+            .map(|i| &self.items[i])
+            .filter(|item| {
+                item.on_side(side)
+                    && (min_x..=max_x).contains(&item.x_mm)
+                    && (min_y..=max_y).contains(&item.y_mm)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_spatial_index() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!R1!1!R0402!NO!0!\n\
+S!R2!1!R0402!YES!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!R1!1!1!0!0!!0.25!\n\
+S!B!R1!2!2!1!0!!0.25!\n\
+S!A!R2!1!1!0!0!!0.25!\n\
+S!C!R2!2!2!0!1!!0.25!\n\
+A!VIAID!NET_NAME!VIA_X!VIA_Y!\n\
+S!V1!B!50!50!\n";
+        let board = board_from(content);
+        let index = SpatialIndex::from_board(&board);
+
+        let hits = index.at(1.1, 0.0, None);
+        assert_eq!(hits.len(), 1);
+        assert_eq!((hits[0].refdes.as_str(), hits[0].pin.as_str()), ("R1", "2"));
+        assert_eq!(hits[0].net_name, "B");
+        assert_eq!(index.at(0.0, 0.0, None).len(), 2);
+        assert_eq!(index.at(0.0, 0.0, Some(Side::Bottom))[0].refdes, "R2");
+
+        let nearest = index.nearest(40.0, 40.0, 2, None);
+        assert_eq!(nearest[0].0.kind, SpatialKind::Via);
+        assert_eq!(nearest[1].0.refdes, "R1");
+        assert_eq!(index.nearest(40.0, 40.0, 1, Some(Side::Bottom)).len(), 1);
+
+        assert_eq!(
+            index.in_rect(-1.0, -1.0, 0.5, 2.0, Some(Side::Top)).len(),
+            1
+        );
+        assert_eq!(index.in_rect(-1.0, -1.0, 0.5, 2.0, None).len(), 3);
+
+        // A through-hole pin is on both sides, even on a bottom component
+        let mut board = board;
+        board.components.get_mut("R2").unwrap().pins[1].pad_type = PadType::ThroughHole;
+        let index = SpatialIndex::from_board(&board);
+        let hits = index.at(0.0, 1.0, Some(Side::Top));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].side, None);
+        assert_eq!(
+            index.in_rect(-1.0, -1.0, 0.5, 2.0, Some(Side::Top)).len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/stackup.rs - Layer stack-up inference for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use super::Board;
+use super::BoardVia;
+use super::NetInfo;
+use super::traces::is_etch_segment;
+use super::traces::open_segments;
+
+/// What a copper layer is used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayerRole {
+    /// A routing layer.
+    Signal,
+    /// A ground plane.
+    Plane,
+    /// A power plane.
+    Power,
+}
+
+/// A copper layer in a [Stackup].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layer {
+    /// The name of the layer (its ETCH subclass, e.g. "TOP" or "GND02").
+    pub name: String,
+    /// The inferred use of the layer.
+    pub role: LayerRole,
+    /// The names of the nets with copper on the layer, in name order.
+    pub nets: Vec<String>,
+}
+
+/// The copper layers of a board, in order from top to bottom.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stackup {
+    /// The layers, from top to bottom.
+    pub layers: Vec<Layer>,
+}
+
+/// Returns the layer number in a layer name (e.g., 2 for "GND02" or 3 for
+/// "IN3_SIG"), if it has one.
+fn layer_number(name: &str) -> Option<u32> {
+    let start = name.find(|c: char| c.is_ascii_digit())?;
+    let digits: String = name[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+/// Orders layer names from top to bottom: TOP first, BOTTOM last, and inner
+/// layers by their number and then by name.
+fn layer_order(name: &str) -> (u8, u32, String) {
+    let upper = name.to_ascii_uppercase();
+    match upper.as_str() {
+        "TOP" => (0, 0, upper),
+        "BOTTOM" => (2, 0, upper),
+        _ => (1, layer_number(&upper).unwrap_or(u32::MAX), upper),
+    }
+}
+
+/// Infers a layer's role from its name, or failing that, from whether its
+/// copper is mostly shapes and which nets they're on.
+fn layer_role(name: &str, traces: usize, shape_nets: &[&str]) -> LayerRole {
+    let upper = name.to_ascii_uppercase();
+    if ["GND", "VSS", "GROUND"].iter().any(|w| upper.contains(w)) {
+        return LayerRole::Plane;
+    }
+    if ["PWR", "POWER", "VCC", "VDD"]
+        .iter()
+        .any(|w| upper.contains(w))
+    {
+        return LayerRole::Power;
+    }
+    if matches!(upper.as_str(), "TOP" | "BOTTOM") || shape_nets.len() <= traces {
+        return LayerRole::Signal;
+    }
+
+    if shape_nets.iter().all(|net| NetInfo::from_name(net).ground) {
+        LayerRole::Plane
+    } else {
+        LayerRole::Power
+    }
+}
+
+impl Stackup {
+    /// Infers the layer stack-up of a board from the subclasses of its ETCH
+    /// graphics and the start and end layers of its vias.
+    ///
+    /// Layers are ordered with TOP first and BOTTOM last, and inner layers by
+    /// the number in their name. Inner layers are planes or power layers if
+    /// their name says so, or if their copper is mostly shapes rather than
+    /// traces, in which case they're planes if every shape is on a ground
+    /// net.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    pub fn from_board(board: &Board) -> Self {
+        #[derive(Default)]
+        struct Usage<'a> {
+            nets: BTreeSet<&'a str>,
+            traces: usize,
+            shape_nets: Vec<&'a str>,
+        }
+
+        let mut usage: BTreeMap<&str, Usage> = BTreeMap::new();
+        for figure in board.figures(is_etch_segment) {
+            let (layer, net_name) = (figure[0].subclass.as_str(), figure[0].net_name.as_str());
+            let layer_usage = usage.entry(layer).or_default();
+            if !net_name.is_empty() {
+                layer_usage.nets.insert(net_name);
+            }
+            let open = open_segments(&figure).len();
+            if open < figure.len() {
+                layer_usage.shape_nets.push(net_name);
+            }
+            if open > 0 {
+                layer_usage.traces += 1;
+            }
+        }
+        for via in &board.vias {
+            for layer in [&via.start_layer, &via.end_layer] {
+                if !layer.is_empty() {
+                    usage.entry(layer.as_str()).or_default();
+                }
+            }
+        }
+
+        let mut layers: Vec<Layer> = usage
+            .into_iter()
+            .map(|(name, usage)| Layer {
+                name: name.to_string(),
+                role: layer_role(name, usage.traces, &usage.shape_nets),
+                nets: usage.nets.into_iter().map(String::from).collect(),
+            })
+            .collect();
+        layers.sort_by_key(|layer| layer_order(&layer.name));
+
+        Self { layers }
+    }
+
+    /// Returns the position of a layer in the stack-up, counting from zero
+    /// at the top.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.layers
+            .iter()
+            .position(|layer| layer.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the layers a via spans, from top to bottom. Vias with no
+    /// known start or end layer are assumed to go through every layer.
+    pub fn via_layers(&self, via: &BoardVia) -> &[Layer] {
+        let start = self.index_of(&via.start_layer).unwrap_or(0);
+        let end = self
+            .index_of(&via.end_layer)
+            .unwrap_or(self.layers.len().saturating_sub(1));
+        let (start, end) = (start.min(end), start.max(end));
+        self.layers.get(start..=end).unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_stackup() {
+        let content = b"A!UNIT!millimeters!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!ETCH!BOTTOM!LINE!1!1 1 0!0!0!10!0!0.1!!!!!CLK!\n\
+S!ETCH!TOP!LINE!2!1 1 0!0!0!10!0!0.1!!!!!DATA!\n\
+S!ETCH!IN3!LINE!3!1 1 0!0!0!10!0!0!!!!!+1V8!\n\
+S!ETCH!IN3!LINE!3!2 1 0!10!0!10!10!0!!!!!+1V8!\n\
+S!ETCH!IN3!LINE!3!3 1 0!10!10!0!0!0!!!!!+1V8!\n\
+S!ETCH!GND02!LINE!4!1 1 0!0!0!10!0!0.1!!!!!CLK!\n\
+A!VIAID!NET_NAME!VIA_X!VIA_Y!RADIUS!START_LAYER!END_LAYER!\n\
+S!V1!CLK!0!0!0.1!TOP!IN3!\n\
+S!V2!CLK!0!0!0.1!IN4!BOTTOM!\n";
+        let board = board_from(content);
+        let stackup = Stackup::from_board(&board);
+
+        let names: Vec<_> = stackup.layers.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["TOP", "GND02", "IN3", "IN4", "BOTTOM"]);
+        let roles: Vec<_> = stackup.layers.iter().map(|l| l.role).collect();
+        assert_eq!(
+            roles,
+            [
+                LayerRole::Signal,
+                LayerRole::Plane,
+                LayerRole::Power,
+                LayerRole::Signal,
+                LayerRole::Signal
+            ]
+        );
+        assert_eq!(stackup.layers[1].nets, ["CLK"]);
+        assert_eq!(stackup.via_layers(&board.vias[0]).len(), 3);
+        assert_eq!(stackup.via_layers(&board.vias[1]).len(), 2);
+    }
+}
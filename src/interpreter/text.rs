@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/text.rs - Text items on the board for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::parser::Units;
+
+use super::BoardGraphic;
+use super::PlacedComponent;
+use super::Primitive;
+use super::board::to_mm;
+
+/// How a text string is aligned to its anchor point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Justification {
+    /// The anchor is at the start of the text.
+    Left,
+    /// The anchor is in the middle of the text.
+    Center,
+    /// The anchor is at the end of the text.
+    Right,
+}
+
+impl Justification {
+    /// Parses a justification field (e.g., "LEFT"). Unknown and empty values
+    /// are treated as left-justified, which is the default for Allegro text.
+    pub fn from_field(field: &str) -> Self {
+        match field.trim().to_ascii_uppercase().as_str() {
+            "CENTER" | "CENTRE" => Self::Center,
+            "RIGHT" => Self::Right,
+            _ => Self::Left,
+        }
+    }
+}
+
+/// The font of a text item.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextFont {
+    /// The number of the text block (font size preset) the text uses.
+    pub block: u32,
+    /// The character width in millimeters, if given.
+    pub width_mm: Option<f64>,
+    /// The character height in millimeters, if given.
+    pub height_mm: Option<f64>,
+}
+
+impl TextFont {
+    /// Parses the font parameters of a text record.
+    ///
+    /// The parameters are a space-separated list that starts with the text
+    /// block number. Some files follow it with the character width and
+    /// height, in the file's units.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units of the file the record is from.
+    /// * `field` - The record's raw font parameters.
+    ///
+    /// # Returns
+    ///
+    /// The font, or `None` if the parameters don't start with a block number.
+    pub fn from_field(units: &Units, field: &str) -> Option<Self> {
+        let mut params = field.split_whitespace();
+        let block = params.next()?.parse().ok()?;
+        let mut size = || -> Option<f64> {
+            let value = Decimal::from_str(&params.next()?.replace(',', ".")).ok()?;
+            to_mm(units, value).to_f64().filter(|&v| v > 0.0)
+        };
+        let width_mm = size();
+        let height_mm = size();
+        Some(Self {
+            block,
+            width_mm,
+            height_mm,
+        })
+    }
+}
+
+/// A text string on the board, such as a reference designator label, a
+/// component value, or a board marking.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextItem {
+    /// The class of the record (e.g., "REF DES"), or an empty string for
+    /// symbol text.
+    pub class: String,
+    /// The layer (subclass) the text is on (e.g., "SILKSCREEN_TOP").
+    pub layer: String,
+    /// The reference designator of the component the text belongs to, or an
+    /// empty string.
+    pub refdes: String,
+    /// The text string.
+    pub text: String,
+    /// The X-coordinate of the anchor point, in millimeters.
+    pub x_mm: f64,
+    /// The Y-coordinate of the anchor point, in millimeters.
+    pub y_mm: f64,
+    /// The counter-clockwise rotation in degrees.
+    pub rotation: f64,
+    /// Whether the text is mirrored, i.e. reads correctly from the bottom
+    /// side of the board.
+    pub mirrored: bool,
+    /// How the text is aligned to its anchor point.
+    pub justification: Justification,
+    /// The font of the text, if its parameters could be parsed.
+    pub font: Option<TextFont>,
+}
+
+impl TextItem {
+    /// Collects the text items from a board's graphics.
+    ///
+    /// Symbol text belongs to the component named by its record. Classed
+    /// text has no reference designator of its own, so it's linked to a
+    /// component only if it's in a REF DES class and its text is the
+    /// component's reference designator.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units of the file the graphics are from.
+    /// * `graphics` - The board's decoded graphics.
+    /// * `components` - The board's components, by reference designator.
+    pub fn from_graphics(
+        units: &Units,
+        graphics: &[BoardGraphic],
+        components: &BTreeMap<String, PlacedComponent>,
+    ) -> Vec<Self> {
+        graphics
+            .iter()
+            .filter_map(|graphic| {
+                let Primitive::Text {
+                    x_mm,
+                    y_mm,
+                    rotation,
+                    mirrored,
+                    ref justification,
+                    ref font,
+                    ref text,
+                } = graphic.primitive
+                else {
+                    return None;
+                };
+
+                let refdes = if !graphic.refdes.is_empty() {
+                    graphic.refdes.clone()
+                } else if graphic.class.to_ascii_uppercase().starts_with("REF")
+                    && components.contains_key(text.trim())
+                {
+                    text.trim().to_string()
+                } else {
+                    String::new()
+                };
+
+                Some(Self {
+                    class: graphic.class.clone(),
+                    layer: graphic.subclass.clone(),
+                    refdes,
+                    text: text.clone(),
+                    x_mm,
+                    y_mm,
+                    rotation,
+                    mirrored,
+                    justification: Justification::from_field(justification),
+                    font: TextFont::from_field(units, font),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_text_items() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!U1!1!SOIC!NO!90!\n\
+S!R1!1!R0402!YES!0!\n\
+A!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!GRAPHIC_DATA_2!\
+GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!GRAPHIC_DATA_8!\
+GRAPHIC_DATA_9!SUBCLASS!SYM_NAME!REFDES!\n\
+S!TEXT!1!1 1 0!10!13!90!NO!CENTER!2 0.8 1.2!U1!!!SILKSCREEN_TOP!SOIC!U1!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!REF DES!SILKSCREEN_BOTTOM!TEXT!2!1 1 0!20!5!0!YES!RIGHT!3! R1 !!!!\n\
+S!REF DES!SILKSCREEN_TOP!TEXT!3!1 1 0!30!5!0!NO!LEFT!4 0 0!U9!!!!\n\
+S!BOARD GEOMETRY!SILKSCREEN_TOP!TEXT!4!1 1 0!40!5!0!NO!!X!R1!!!!\n";
+        let board = board_from(content);
+        let texts = &board.texts;
+        assert_eq!(texts.len(), 4);
+
+        // Symbol text belongs to the component in its record
+        let label = &texts[0];
+        assert_eq!((label.refdes.as_str(), label.text.as_str()), ("U1", "U1"));
+        assert_eq!(label.justification, Justification::Center);
+        assert_eq!((label.x_mm, label.y_mm, label.rotation), (10.0, 13.0, 90.0));
+        let font = label.font.unwrap();
+        assert_eq!(font.block, 2);
+        assert_eq!((font.width_mm, font.height_mm), (Some(0.8), Some(1.2)));
+
+        // REF DES text is linked by its text
+        let r1 = &texts[1];
+        assert_eq!((r1.class.as_str(), r1.refdes.as_str()), ("REF DES", "R1"));
+        assert_eq!(r1.layer, "SILKSCREEN_BOTTOM");
+        assert!(r1.mirrored);
+        assert_eq!(r1.justification, Justification::Right);
+        let font = r1.font.unwrap();
+        assert_eq!((font.block, font.width_mm, font.height_mm), (3, None, None));
+
+        // ...but only if there's a component with that reference designator
+        let u9 = &texts[2];
+        assert_eq!(u9.refdes, "");
+        assert_eq!(u9.justification, Justification::Left);
+        let font = u9.font.unwrap();
+        assert_eq!((font.block, font.width_mm, font.height_mm), (4, None, None));
+
+        // Other classes are never linked
+        let marking = &texts[3];
+        assert_eq!(marking.refdes, "");
+        assert_eq!(marking.justification, Justification::Left);
+        assert_eq!(marking.font, None);
+    }
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/traces.rs - Copper trace reconstruction for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use super::Board;
+use super::BoardGraphic;
+use super::Primitive;
+use super::graphics::EndpointGraph;
+use super::graphics::chain_loops;
+use super::graphics::join_paths;
+
+/// How far apart, in millimeters, the ends of two copper segments may be and
+/// still be considered connected.
+pub(crate) const COPPER_TOLERANCE_MM: f64 = 0.01;
+
+/// A routed trace: a run of connected copper segments of one width on one
+/// layer.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trace {
+    /// The name of the net the trace is on, or an empty string if it's on
+    /// none.
+    pub net_name: String,
+    /// The layer (ETCH subclass) the trace is on.
+    pub layer: String,
+    /// The width of the trace in millimeters.
+    pub width_mm: f64,
+    /// The points along the trace, in millimeters, with arcs flattened.
+    pub points: Vec<(f64, f64)>,
+    /// The length of the trace in millimeters, measured along its arcs.
+    pub length_mm: f64,
+}
+
+/// The routed copper of one net.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetRouting {
+    /// The traces of the net.
+    pub traces: Vec<Trace>,
+    /// The total length of the net's traces, in millimeters.
+    pub length_mm: f64,
+    /// The layers the net's traces are on, in name order.
+    pub layers: Vec<String>,
+}
+
+/// The routed copper of the board, from the line and arc segments in the
+/// ETCH class.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Routing {
+    /// The routing of each net, by net name. Copper on no net is under the
+    /// empty name.
+    pub nets: BTreeMap<String, NetRouting>,
+}
+
+/// Returns whether a graphic is a copper line or arc segment.
+pub(crate) fn is_etch_segment(graphic: &BoardGraphic) -> bool {
+    graphic.class.eq_ignore_ascii_case("ETCH")
+        && matches!(
+            graphic.primitive,
+            Primitive::Line { .. } | Primitive::Arc { .. }
+        )
+}
+
+/// Returns the segments of a figure that don't close into a loop, in figure
+/// order. Segments that do are the boundary of a shape rather than a trace.
+pub(crate) fn open_segments<'a>(figure: &[&'a BoardGraphic]) -> Vec<&'a BoardGraphic> {
+    let closed: BTreeSet<usize> =
+        chain_loops(figure.iter().map(|g| &g.primitive), COPPER_TOLERANCE_MM)
+            .into_iter()
+            .flat_map(|(indices, _)| indices)
+            .collect();
+    figure
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !closed.contains(i))
+        .map(|(_, g)| *g)
+        .collect()
+}
+
+fn segment_width(primitive: &Primitive) -> f64 {
+    match *primitive {
+        Primitive::Line { width_mm, .. } | Primitive::Arc { width_mm, .. } => width_mm,
+        _ => 0.0,
+    }
+}
+
+impl Routing {
+    /// Reconstructs the routed copper of a board.
+    ///
+    /// Segments are grouped by layer, net, and graphic data number, and
+    /// segments of the same width are joined into one trace wherever exactly
+    /// two of their ends meet, whatever order they're in. Segments that close into a loop outline copper shapes
+    /// rather than traces, so they're skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    pub fn from_board(board: &Board) -> Self {
+        let mut nets: BTreeMap<String, NetRouting> = BTreeMap::new();
+
+        for figure in board.figures(is_etch_segment) {
+            let segments = open_segments(&figure);
+            if segments.is_empty() {
+                continue;
+            }
+
+            // Segments of different widths are never joined
+            let mut widths: Vec<(f64, Vec<&BoardGraphic>)> = Vec::new();
+            for graphic in segments {
+                let width_mm = segment_width(&graphic.primitive);
+                match widths.iter_mut().find(|(w, _)| *w == width_mm) {
+                    Some((_, group)) => group.push(graphic),
+                    None => widths.push((width_mm, vec![graphic])),
+                }
+            }
+
+            let routing = nets.entry(figure[0].net_name.clone()).or_default();
+            for (width_mm, group) in widths {
+                let paths: Vec<Vec<(f64, f64)>> =
+                    group.iter().map(|g| g.primitive.path()).collect();
+                let graph = EndpointGraph::new(&paths, COPPER_TOLERANCE_MM);
+                for steps in graph.chains() {
+                    routing.traces.push(Trace {
+                        net_name: group[0].net_name.clone(),
+                        layer: group[0].subclass.clone(),
+                        width_mm,
+                        points: join_paths(&paths, &steps),
+                        length_mm: steps
+                            .iter()
+                            .map(|&(i, _)| group[i].primitive.length_mm().unwrap_or(0.0))
+                            .sum(),
+                    });
+                }
+            }
+        }
+
+        for routing in nets.values_mut() {
+            routing.length_mm = routing.traces.iter().map(|t| t.length_mm).sum();
+            let layers: BTreeSet<&String> = routing.traces.iter().map(|t| &t.layer).collect();
+            routing.layers = layers.into_iter().cloned().collect();
+        }
+
+        Self { nets }
+    }
+
+    /// Returns the routing of a net.
+    pub fn net(&self, name: &str) -> Option<&NetRouting> {
+        self.nets.get(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_routing() {
+        let content = b"A!UNIT!millimeters!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!ETCH!TOP!LINE!1!1 1 0!0!0!10!0!0.1!!!!!CLK!\n\
+S!ETCH!TOP!ARC!1!2 1 0!10!0!15!5!10!5!5!0.1!COUNTERCLOCKWISE!CLK!\n\
+S!ETCH!TOP!LINE!1!3 1 0!15!5!15!20!0.2!!!!!CLK!\n\
+S!ETCH!BOTTOM!LINE!2!1 1 0!0!0!0!3!0.1!!!!!CLK!\n\
+S!ETCH!GND02!LINE!3!1 1 0!0!0!10!0!0!!!!!GND!\n\
+S!ETCH!GND02!LINE!3!2 1 0!10!0!10!10!0!!!!!GND!\n\
+S!ETCH!GND02!LINE!3!3 1 0!10!10!0!0!0!!!!!GND!\n\
+S!ETCH!TOP!LINE!4!1 1 0!20!0!30!0!0.1!!!!!DATA!\n\
+S!ETCH!TOP!LINE!4!2 1 0!30!0!30!10!0.1!!!!!DATA!\n\
+S!ETCH!TOP!LINE!4!3 1 0!30!10!20!0!0.1!!!!!DATA!\n\
+S!ETCH!TOP!LINE!4!4 1 0!30!10!30!14!0.1!!!!!DATA!\n\
+S!ETCH!TOP!LINE!5!1 1 0!30!10!30!14!0.1!!!!!ADDR!\n\
+S!ETCH!TOP!LINE!5!2 1 0!20!0!30!0!0.1!!!!!ADDR!\n\
+S!ETCH!TOP!LINE!5!3 1 0!30!10!20!0!0.1!!!!!ADDR!\n\
+S!ETCH!TOP!LINE!5!4 1 0!30!0!30!10!0.1!!!!!ADDR!\n\
+S!ETCH!TOP!LINE!6!1 1 0!20!0!30!0!0.1!!!!!SHUF!\n\
+S!ETCH!TOP!LINE!6!2 1 0!40!0!30!0!0.1!!!!!SHUF!\n\
+S!ETCH!TOP!LINE!6!3 1 0!20!0!10!0!0.1!!!!!SHUF!\n";
+        let board = board_from(content);
+        let routing = Routing::from_board(&board);
+
+        let clk = routing.net("CLK").unwrap();
+        assert_eq!(clk.traces.len(), 3);
+        assert_eq!(clk.layers, ["BOTTOM", "TOP"]);
+        let quarter = std::f64::consts::PI * 5.0 / 2.0;
+        assert!((clk.traces[0].length_mm - (10.0 + quarter)).abs() < 1e-9);
+        assert!((clk.length_mm - (10.0 + quarter + 15.0 + 3.0)).abs() < 1e-9);
+        assert!(routing.net("GND").is_none());
+
+        // Only the stub off the loop is a trace
+        let data = routing.net("DATA").unwrap();
+        assert_eq!(data.traces.len(), 1);
+        assert_eq!(data.traces[0].points, [(30.0, 10.0), (30.0, 14.0)]);
+
+        // The same shape with its segments in another order
+        let addr = routing.net("ADDR").unwrap();
+        assert_eq!(addr.traces.len(), 1);
+        assert_eq!(addr.traces[0].points, [(30.0, 10.0), (30.0, 14.0)]);
+
+        // Segments out of order and direction are joined into one trace
+        let shuf = routing.net("SHUF").unwrap();
+        assert_eq!(shuf.traces.len(), 1);
+        assert_eq!(
+            shuf.traces[0].points,
+            [(10.0, 0.0), (20.0, 0.0), (30.0, 0.0), (40.0, 0.0)]
+        );
+        assert!((shuf.length_mm - 30.0).abs() < 1e-9);
+    }
+}
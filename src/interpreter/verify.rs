@@ -0,0 +1,497 @@
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+/*
+ *  src/interpreter/verify.rs - Copper connectivity check for ASUS FZ and ASRock CAE files.
+ *  Copyright (C) 2026  Forest Crossman <cyrozap@gmail.com>
+ *
+ *  This program is free software: you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation, either version 3 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use super::Board;
+use super::Netlist;
+use super::PadType;
+use super::Primitive;
+use super::Rect;
+use super::Shape;
+use super::Shapes;
+use super::Side;
+use super::traces::COPPER_TOLERANCE_MM;
+use super::traces::is_etch_segment;
+use super::traces::open_segments;
+
+/// A disagreement between the copper on the board and the declared nets.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CopperIssue {
+    /// The copper of a net is split into several unconnected islands.
+    SplitNet {
+        /// The name of the net.
+        net_name: String,
+        /// The number of islands.
+        islands: usize,
+    },
+    /// Copper on one net touches copper, a pin, or a via on another net.
+    NetConflict {
+        /// The name of the net of the copper segment or shape.
+        net_name: String,
+        /// The name of the net of the copper it touches.
+        other_net_name: String,
+        /// The layer the contact is on.
+        layer: String,
+        /// The X-coordinate of the contact, in millimeters.
+        x_mm: f64,
+        /// The Y-coordinate of the contact, in millimeters.
+        y_mm: f64,
+    },
+    /// A pin on a routed net has no copper connected to it.
+    UnroutedPin {
+        /// The reference designator of the pin's component.
+        refdes: String,
+        /// The number of the pin.
+        pin: String,
+        /// The name of the pin's net.
+        net_name: String,
+    },
+}
+
+/// The result of checking the copper of a board against its netlist.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CopperCheck {
+    /// The problems found, in the order: conflicts, split nets, unrouted
+    /// pins.
+    pub issues: Vec<CopperIssue>,
+}
+
+enum Geometry {
+    /// A pin or via. Pins only touch copper on their own side's outer layer,
+    /// while vias touch copper on every layer.
+    Disc {
+        x: f64,
+        y: f64,
+        r: f64,
+        side: Option<Side>,
+    },
+    /// A copper segment.
+    Path {
+        points: Vec<(f64, f64)>,
+        half_width: f64,
+    },
+    /// A filled copper shape.
+    Area(Shape),
+}
+
+struct Item {
+    net_name: String,
+    layer: String,
+    geometry: Geometry,
+    /// The pin this item is, if any, as (refdes, pin number).
+    pin: Option<(String, String)>,
+}
+
+impl Item {
+    fn bounds(&self) -> Option<Rect> {
+        match &self.geometry {
+            Geometry::Disc { x, y, r, .. } => Rect::from_discs([(*x, *y, *r)]),
+            Geometry::Path { points, half_width } => {
+                Rect::from_discs(points.iter().map(|&(x, y)| (x, y, *half_width)))
+            }
+            Geometry::Area(shape) => shape.outline.bounds(),
+        }
+    }
+
+    fn is_copper(&self) -> bool {
+        !matches!(self.geometry, Geometry::Disc { .. })
+    }
+
+    /// Returns whether copper on this item's layer is on the outer layer of a
+    /// side.
+    fn on_side(&self, side: Side) -> bool {
+        let layer = self.layer.to_ascii_uppercase();
+        match side {
+            Side::Top => layer == "TOP",
+            Side::Bottom => layer == "BOTTOM",
+        }
+    }
+}
+
+/// Returns the distance from a point to a polyline.
+fn distance_to_path((px, py): (f64, f64), points: &[(f64, f64)]) -> f64 {
+    if points.len() == 1 {
+        return (px - points[0].0).hypot(py - points[0].1);
+    }
+    points
+        .windows(2)
+        .map(|w| {
+            let ((x1, y1), (x2, y2)) = (w[0], w[1]);
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let len2 = dx * dx + dy * dy;
+            let t = if len2 == 0.0 {
+                0.0
+            } else {
+                (((px - x1) * dx + (py - y1) * dy) / len2).clamp(0.0, 1.0)
+            };
+            (px - (x1 + t * dx)).hypot(py - (y1 + t * dy))
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Returns where a copper item touches another item, if it does.
+///
+/// Segments touch whatever their ends land on: vias on any layer, pins on
+/// their own side's outer layer, and other copper on the segment's own layer.
+/// Shapes touch the pins and vias inside them, with the same rule for pins.
+fn contact(copper: &Item, other: &Item) -> Option<(f64, f64)> {
+    let tolerance = COPPER_TOLERANCE_MM;
+    let same_layer = copper.layer.eq_ignore_ascii_case(&other.layer);
+    match (&copper.geometry, &other.geometry) {
+        (Geometry::Path { points, half_width }, other_geometry) => {
+            let ends = [points[0], points[points.len() - 1]];
+            ends.into_iter().find(|&end| match other_geometry {
+                Geometry::Disc { x, y, r, side } => {
+                    side.is_none_or(|side| copper.on_side(side))
+                        && (end.0 - x).hypot(end.1 - y) <= r + half_width + tolerance
+                }
+                Geometry::Path {
+                    points: other_points,
+                    half_width: other_half_width,
+                } => {
+                    same_layer
+                        && distance_to_path(end, other_points) <= other_half_width + tolerance
+                }
+                Geometry::Area(shape) => same_layer && shape.contains(end.0, end.1),
+            })
+        }
+        (Geometry::Area(shape), Geometry::Disc { x, y, side, .. }) => {
+            let reaches = side.is_none_or(|side| copper.on_side(side));
+            (reaches && shape.contains(*x, *y)).then_some((*x, *y))
+        }
+        _ => None,
+    }
+}
+
+fn find(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+impl CopperCheck {
+    /// Checks the copper of a board against its netlist.
+    ///
+    /// Copper segments and shapes from the ETCH class, pins, and vias are
+    /// connected wherever they touch, within a small tolerance. Items on
+    /// different nets are never connected, and each place they touch is
+    /// reported as a conflict. Copper with no net name takes on the net of
+    /// whatever it touches.
+    ///
+    /// Split nets and unrouted pins are only reported for nets with at least
+    /// two pins and some copper, and never for no-connect nets. Boards with
+    /// no ETCH data have no issues.
+    ///
+    /// # Arguments
+    ///
+    /// * `board` - The board model.
+    /// * `netlist` - The board's netlist.
+    pub fn from_board(board: &Board, netlist: &Netlist) -> Self {
+        let f = |value: Decimal| value.to_f64().unwrap_or(0.0);
+        let mut items: Vec<Item> = Vec::new();
+
+        for figure in board.figures(is_etch_segment) {
+            let (net_name, layer) = (&figure[0].net_name, &figure[0].subclass);
+            for graphic in open_segments(&figure) {
+                let half_width = match graphic.primitive {
+                    Primitive::Line { width_mm, .. } | Primitive::Arc { width_mm, .. } => {
+                        width_mm / 2.0
+                    }
+                    _ => 0.0,
+                };
+                items.push(Item {
+                    net_name: net_name.clone(),
+                    layer: layer.clone(),
+                    geometry: Geometry::Path {
+                        points: graphic.primitive.path(),
+                        half_width,
+                    },
+                    pin: None,
+                });
+            }
+        }
+        for shape in Shapes::from_board(board).shapes {
+            if shape.class.eq_ignore_ascii_case("ETCH") {
+                items.push(Item {
+                    net_name: shape.net_name.clone(),
+                    layer: shape.layer.clone(),
+                    geometry: Geometry::Area(shape),
+                    pin: None,
+                });
+            }
+        }
+        if items.is_empty() {
+            return Self { issues: Vec::new() };
+        }
+
+        for (refdes, component) in &board.components {
+            for pin in &component.pins {
+                items.push(Item {
+                    net_name: pin.net_name.clone(),
+                    layer: String::new(),
+                    geometry: Geometry::Disc {
+                        x: f(pin.x_mm),
+                        y: f(pin.y_mm),
+                        r: f(pin.radius_mm),
+                        side: match pin.pad_type {
+                            PadType::ThroughHole => None,
+                            PadType::Smd => Some(component.side),
+                        },
+                    },
+                    pin: Some((refdes.clone(), pin.number.clone())),
+                });
+            }
+        }
+        for via in &board.vias {
+            items.push(Item {
+                net_name: via.net_name.clone(),
+                layer: String::new(),
+                geometry: Geometry::Disc {
+                    x: f(via.x_mm),
+                    y: f(via.y_mm),
+                    r: via.radius_mm.map_or(0.0, f),
+                    side: None,
+                },
+                pin: None,
+            });
+        }
+
+        // Bucket the items by location so that only nearby items are
+        // compared
+        const CELL_MM: f64 = 2.0;
+        let cell = |v: f64| (v / CELL_MM).floor() as i64;
+        let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            let Some(b) = item.bounds() else {
+                continue;
+            };
+            let b = b.expanded(COPPER_TOLERANCE_MM);
+            for cx in cell(b.min_x_mm)..=cell(b.max_x_mm) {
+                for cy in cell(b.min_y_mm)..=cell(b.max_y_mm) {
+                    grid.entry((cx, cy)).or_default().push(i);
+                }
+            }
+        }
+
+        let mut parents: Vec<usize> = (0..items.len()).collect();
+        let mut touched = vec![false; items.len()];
+        let mut conflicts: BTreeSet<(String, String, String, i64, i64)> = BTreeSet::new();
+        let mut issues = Vec::new();
+
+        let mut pairs: BTreeSet<(usize, usize)> = BTreeSet::new();
+        for bucket in grid.values() {
+            for &a in bucket {
+                if !items[a].is_copper() {
+                    continue;
+                }
+                for &b in bucket {
+                    if a != b && !(items[b].is_copper() && b < a) {
+                        pairs.insert((a, b));
+                    }
+                }
+            }
+        }
+
+        for (a, b) in pairs {
+            let point = contact(&items[a], &items[b]).or_else(|| {
+                items[b]
+                    .is_copper()
+                    .then(|| contact(&items[b], &items[a]))
+                    .flatten()
+            });
+            let Some((x, y)) = point else {
+                continue;
+            };
+            touched[a] = true;
+            touched[b] = true;
+
+            let (net_a, net_b) = (&items[a].net_name, &items[b].net_name);
+            if !net_a.is_empty() && !net_b.is_empty() && net_a != net_b {
+                // Report each place only once, to the nearest micrometer
+                let key = (
+                    net_a.min(net_b).clone(),
+                    net_a.max(net_b).clone(),
+                    items[a].layer.clone(),
+                    (x * 1000.0).round() as i64,
+                    (y * 1000.0).round() as i64,
+                );
+                if conflicts.insert(key) {
+                    issues.push(CopperIssue::NetConflict {
+                        net_name: net_a.clone(),
+                        other_net_name: net_b.clone(),
+                        layer: items[a].layer.clone(),
+                        x_mm: x,
+                        y_mm: y,
+                    });
+                }
+                continue;
+            }
+
+            let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+            parents[root_a] = root_b;
+        }
+
+        let reportable = |name: &str| {
+            netlist
+                .net(name)
+                .is_some_and(|net| !net.no_connect && net.pins.len() >= 2)
+        };
+
+        let mut islands: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+        for i in 0..items.len() {
+            if (touched[i] || items[i].is_copper()) && reportable(&items[i].net_name) {
+                let root = find(&mut parents, i);
+                islands
+                    .entry(items[i].net_name.as_str())
+                    .or_default()
+                    .insert(root);
+            }
+        }
+        for (net_name, roots) in &islands {
+            if roots.len() > 1 {
+                issues.push(CopperIssue::SplitNet {
+                    net_name: net_name.to_string(),
+                    islands: roots.len(),
+                });
+            }
+        }
+
+        for (i, item) in items.iter().enumerate() {
+            if let Some((refdes, pin)) = &item.pin
+                && !touched[i]
+                && islands.contains_key(item.net_name.as_str())
+            {
+                issues.push(CopperIssue::UnroutedPin {
+                    refdes: refdes.clone(),
+                    pin: pin.clone(),
+                    net_name: item.net_name.clone(),
+                });
+            }
+        }
+
+        Self { issues }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpreter::board_from;
+
+    #[test]
+    fn test_copper_check() {
+        let content = b"A!UNIT!millimeters!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!U1!1!1!0!0!!0.2!\n\
+S!A!U2!1!1!10!0!!0.2!\n\
+S!A!U3!1!1!30!0!!0.2!\n\
+S!B!U1!2!2!0!5!!0.2!\n\
+S!B!U2!2!2!10!5!!0.2!\n\
+S!C!U1!3!3!0!10!!0.2!\n\
+S!C!U2!3!3!10!10!!0.2!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!ETCH!TOP!LINE!1!1 1 0!0!0!10!0!0.1!!!!!A!\n\
+S!ETCH!TOP!LINE!2!1 1 0!20!0!22!0!0.1!!!!!A!\n\
+S!ETCH!TOP!LINE!3!1 1 0!0!5!10!5!0.1!!!!!B!\n\
+S!ETCH!TOP!LINE!4!1 1 0!0!10!5!10!0.1!!!!!C!\n\
+S!ETCH!TOP!LINE!4!2 1 0!5!10!10!5!0.1!!!!!C!\n";
+        let board = board_from(content);
+        let check = CopperCheck::from_board(&board, &Netlist::from_board(&board));
+
+        assert_eq!(
+            check.issues,
+            [
+                CopperIssue::NetConflict {
+                    net_name: "B".into(),
+                    other_net_name: "C".into(),
+                    layer: "TOP".into(),
+                    x_mm: 10.0,
+                    y_mm: 5.0,
+                },
+                CopperIssue::SplitNet {
+                    net_name: "A".into(),
+                    islands: 2,
+                },
+                CopperIssue::UnroutedPin {
+                    refdes: "U2".into(),
+                    pin: "3".into(),
+                    net_name: "C".into(),
+                },
+                CopperIssue::UnroutedPin {
+                    refdes: "U3".into(),
+                    pin: "1".into(),
+                    net_name: "A".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_copper_check_sides() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!U1!1!SOT23!NO!0!\n\
+S!U2!1!SOT23!YES!0!\n\
+S!U3!1!SOT23!NO!0!\n\
+S!U4!1!SOT23!NO!0!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!U1!1!1!0!0!!0.2!\n\
+S!A!U4!1!1!20!0!!0.2!\n\
+S!B!U2!1!1!10!0!!0.2!\n\
+S!B!U3!1!1!10!5!!0.2!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!ETCH!TOP!LINE!1!1 1 0!0!0!10!0!0.1!!!!!A!\n\
+S!ETCH!TOP!LINE!1!2 1 0!10!0!20!0!0.1!!!!!A!\n\
+S!ETCH!BOTTOM!LINE!2!1 1 0!10!0!10!5!0.1!!!!!B!\n";
+        let board = board_from(content);
+        let check = CopperCheck::from_board(&board, &Netlist::from_board(&board));
+
+        // The top trace ends over the bottom pin U2.1 without touching it,
+        // and the bottom trace doesn't reach the top pin U3.1
+        assert_eq!(
+            check.issues,
+            [CopperIssue::UnroutedPin {
+                refdes: "U3".into(),
+                pin: "1".into(),
+                net_name: "B".into(),
+            }]
+        );
+
+        // A through-hole pin is on both sides, so the bottom trace reaches it
+        let mut board = board;
+        board.components.get_mut("U3").unwrap().pins[0].pad_type = PadType::ThroughHole;
+        let check = CopperCheck::from_board(&board, &Netlist::from_board(&board));
+        assert_eq!(check.issues, []);
+    }
+}
@@ -29,7 +29,9 @@
  *
  * 1. [decoder]: Handles decryption and decompression of the file.
  * 2. [parser]: Converts the decoded bytes into structured data.
- * 3. [interpreter]: Transforms parsed data into usable footprint information.
+ * 3. [interpreter]: Transforms parsed data into a board placement model and
+ *    usable footprint information.
+ * 4. [export]: Writes interpreted data in formats other tools can read.
  *
  * ## Usage Example
  *
@@ -66,9 +68,95 @@
  *     Ok(())
  * }
  * ```
+ *
+ * ## Features
+ *
+ * * `serde`: Implements `Serialize` and `Deserialize` for all public types
+ *   except [parser::RawLine], which holds the original bytes of a parsed line.
+ *   Decimal values are serialized as strings so that no precision is lost.
+ *   The JSON form of a parsed file is described by [JSON_SCHEMA]. Also
+ *   enables the `export::json` module.
  */
 
 mod crypto;
 pub mod decoder;
+pub mod export;
 pub mod interpreter;
 pub mod parser;
+
+/// The version of [JSON_SCHEMA]. This is incremented whenever a change to a
+/// public type changes its serialized form.
+#[cfg(feature = "serde")]
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// A JSON Schema for documents of the form `{"schema_version": ...,
+/// "parsed": ..., "interpreted": ...}`, where `schema_version` is
+/// [JSON_SCHEMA_VERSION], `parsed` is a serialized
+/// [parser::ParsedPcbRepairFile], and the optional `interpreted` is a
+/// serialized [interpreter::InterpretedPcbRepairFile].
+#[cfg(feature = "serde")]
+pub const JSON_SCHEMA: &str = include_str!("../schema/pcbrepair.schema.json");
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use interpreter::InterpretedPcbRepairFile;
+    use parser::{Content, Description, ParsedPcbRepairFile};
+
+    #[test]
+    fn test_json_schema() {
+        let content = b"A!UNIT!millimeters!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!R1!1!R0402!YES!90!\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!A!R1!1!1!10!10!!0.25!\n\
+A!LOGOInfo!ASUS!\n\
+S!B!R1!2!2!11!10!!0.25!\n\
+A!VIAID!NET_NAME!VIA_X!VIA_Y!RADIUS!NOTE!\n\
+S!V1!A!12!10!0.2!BURIED!\n\
+S!V2!A!x!10!0.2!!\n\
+A!TESTVIA!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!VIA_X!VIA_Y!TEST_POINT!RADIUS!\n\
+S!TP1!A!R1!1!1!10!10!T!0.3!\n\
+A!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!GRAPHIC_DATA_2!\
+GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!GRAPHIC_DATA_8!\
+GRAPHIC_DATA_9!SUBCLASS!SYM_NAME!REFDES!\n\
+S!RECTANGLE!1!1 1 0!9.5!9.5!11.5!10.5!0!!!!!SILKSCREEN_TOP!R0402!R1!\n\
+A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!GRAPHIC_DATA_7!\
+GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!\n\
+S!ETCH!TOP!LINE!1!1 1 0!10!10!12!10!0.1!!!!!A!\n\
+\n";
+        let description = b"B|1|B-PRO|1A|PN|EXTRA\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\tNOTE\n\
+01234\tRES 10K 1% 0402\tN/A\tR1\t\tDNP\n\
+\tx\n";
+        let parsed = ParsedPcbRepairFile {
+            content: Content::from_bytes(content).unwrap(),
+            description: Description::from_bytes(description).unwrap(),
+        };
+        assert!(!parsed.content.warnings.is_empty());
+        assert!(!parsed.description.warnings.is_empty());
+        let interpreted = InterpretedPcbRepairFile::from_parsed(&parsed).unwrap();
+        let document = serde_json::json!({
+            "schema_version": JSON_SCHEMA_VERSION,
+            "parsed": parsed,
+            "interpreted": interpreted,
+        });
+
+        let schema: serde_json::Value = serde_json::from_str(JSON_SCHEMA).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&document)
+            .map(|e| format!("{} at {}", e, e.instance_path()))
+            .collect();
+        assert!(errors.is_empty(), "{:#?}", errors);
+
+        // Decimals must be strings, and the version must match
+        let mut invalid = document.clone();
+        invalid["parsed"]["content"]["pins"][0]["pin_x"] = serde_json::json!(10.0);
+        assert!(!validator.is_valid(&invalid));
+        let mut invalid = document;
+        invalid["schema_version"] = serde_json::json!(JSON_SCHEMA_VERSION + 1);
+        assert!(!validator.is_valid(&invalid));
+    }
+}
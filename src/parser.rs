@@ -54,10 +54,13 @@
  * ```
  */
 
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::str::FromStr;
 use std::string::String;
 
 use csv;
+use encoding_rs::Encoding;
 use rust_decimal::Decimal;
 
 use crate::decoder::DecodedPcbRepairFile;
@@ -72,8 +75,187 @@ enum ParserState {
     ClassedGraphicData,
 }
 
+impl ParserState {
+    fn section_kind(&self) -> SectionKind {
+        match self {
+            ParserState::Symbol => SectionKind::Symbols,
+            ParserState::Pin => SectionKind::Pins,
+            ParserState::TestVia => SectionKind::TestVias,
+            ParserState::GraphicData => SectionKind::GraphicData,
+            ParserState::ClassedGraphicData => SectionKind::ClassedGraphicData,
+            ParserState::Via => SectionKind::Vias,
+            ParserState::Unknown => SectionKind::Other,
+        }
+    }
+
+    /// The number of fields a record needs to be parsed in this state.
+    fn min_fields(&self) -> usize {
+        match self {
+            ParserState::Symbol => 6,
+            ParserState::Pin => 9,
+            ParserState::TestVia => 10,
+            ParserState::GraphicData | ParserState::ClassedGraphicData => 16,
+            ParserState::Via | ParserState::Unknown => 1,
+        }
+    }
+}
+
+/// The character encoding of the text fields in a decoded file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextEncoding {
+    /// UTF-8, which also covers plain ASCII files.
+    Utf8,
+    /// GBK (Simplified Chinese).
+    Gbk,
+    /// Big5 (Traditional Chinese).
+    Big5,
+    /// Shift-JIS (Japanese).
+    ShiftJis,
+}
+
+impl TextEncoding {
+    /// The legacy encodings tried by [TextEncoding::detect], in order of
+    /// preference.
+    const LEGACY: [TextEncoding; 3] = [
+        TextEncoding::Gbk,
+        TextEncoding::Big5,
+        TextEncoding::ShiftJis,
+    ];
+
+    fn encoding(self) -> &'static Encoding {
+        match self {
+            TextEncoding::Utf8 => encoding_rs::UTF_8,
+            TextEncoding::Gbk => encoding_rs::GBK,
+            TextEncoding::Big5 => encoding_rs::BIG5,
+            TextEncoding::ShiftJis => encoding_rs::SHIFT_JIS,
+        }
+    }
+
+    /// Scores how likely a character is to appear in text written in this
+    /// encoding, based on which region of the code table it's encoded in.
+    /// Bytes in one legacy encoding usually decode to rare characters or
+    /// symbols in the others.
+    fn char_score(self, c: char) -> i64 {
+        let mut buf = [0; 4];
+        let bytes = self.encode(c.encode_utf8(&mut buf));
+        match (self, bytes.as_slice()) {
+            // GB2312 hanzi and symbols
+            (TextEncoding::Gbk, [0xB0..=0xF7, 0xA1..=0xFE]) => 2,
+            (TextEncoding::Gbk, [0xA1..=0xA9, 0xA1..=0xFE]) => 1,
+            // Frequently used hanzi, symbols, and less frequently used hanzi
+            (TextEncoding::Big5, [0xA4..=0xC6, _]) => 2,
+            (TextEncoding::Big5, [0xA1..=0xA3, _]) => 1,
+            (TextEncoding::Big5, [0xC9..=0xF9, _]) => 0,
+            // Kana and JIS level 1 kanji, symbols, and JIS level 2 kanji
+            (TextEncoding::ShiftJis, [0x82..=0x83 | 0x88..=0x98, _]) => 2,
+            (TextEncoding::ShiftJis, [0x81, _]) => 1,
+            (TextEncoding::ShiftJis, [0x99..=0x9F | 0xE0..=0xEA, _]) => 0,
+            _ => -1,
+        }
+    }
+
+    /// Guesses the encoding of some text.
+    ///
+    /// Valid UTF-8 is always detected as [TextEncoding::Utf8]. Otherwise, each
+    /// legacy encoding that decodes the text without errors is scored by how
+    /// common the decoded characters are in that encoding, and the
+    /// best-scoring one is chosen.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The text to examine.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if std::str::from_utf8(bytes).is_ok() {
+            return TextEncoding::Utf8;
+        }
+
+        let mut best = None;
+        for candidate in Self::LEGACY {
+            let Some(text) = candidate
+                .encoding()
+                .decode_without_bom_handling_and_without_replacement(bytes)
+            else {
+                continue;
+            };
+            let score: i64 = text
+                .chars()
+                .filter(|c| !c.is_ascii())
+                .map(|c| candidate.char_score(c))
+                .sum();
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((candidate, score));
+            }
+        }
+
+        match best {
+            Some((encoding, _)) => encoding,
+            None => TextEncoding::Utf8,
+        }
+    }
+
+    /// Decodes text in this encoding, replacing malformed sequences with
+    /// U+FFFD REPLACEMENT CHARACTER.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        self.encoding()
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned()
+    }
+
+    /// Encodes text in this encoding. Characters that can't be represented are
+    /// written as HTML numeric character references, as is usual for these
+    /// encodings.
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        self.encoding().encode(text).0.into_owned()
+    }
+}
+
+impl FromStr for TextEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "utf8" => Ok(TextEncoding::Utf8),
+            "gbk" | "gb2312" | "gb18030" | "cp936" => Ok(TextEncoding::Gbk),
+            "big5" | "cp950" => Ok(TextEncoding::Big5),
+            "shiftjis" | "sjis" | "cp932" => Ok(TextEncoding::ShiftJis),
+            _ => Err(format!("Unknown text encoding {:?}", s)),
+        }
+    }
+}
+
+/// The line terminator used in a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineEnding {
+    /// Lines end with `"\n"`.
+    Lf,
+    /// Lines end with `"\r\n"`.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the line ending of the first line in `bytes`, defaulting to
+    /// [LineEnding::CrLf] if there is only one line.
+    fn detect(bytes: &[u8]) -> Self {
+        match bytes.iter().position(|&b| b == b'\n') {
+            Some(i) if i == 0 || bytes[i - 1] != b'\r' => LineEnding::Lf,
+            _ => LineEnding::CrLf,
+        }
+    }
+
+    fn as_bytes(self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+        }
+    }
+}
+
 /// Represents the unit system used in the file (mils or millimeters).
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Units {
     /// Unit is in mils (1/1000 inch).
     Mils,
@@ -83,6 +265,7 @@ pub enum Units {
 
 /// Represents a symbol in the decoded PCB file.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Symbol {
     /// The reference designator (e.g., "U1") of the symbol.
     pub refdes: String,
@@ -94,10 +277,14 @@ pub struct Symbol {
     pub sym_mirror: bool,
     /// The rotation angle of the symbol in degrees.
     pub sym_rotate: u16,
+    /// The record's original form, if it was parsed. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Option<RawLine>,
 }
 
 /// Represents a pin in the decoded PCB file.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pin {
     /// The name of the net this pin is connected to.
     pub net_name: String,
@@ -114,10 +301,14 @@ pub struct Pin {
     pub test_point: String,
     /// The radius of the pin on the PCB, in [Content::units] units.
     pub radius: Decimal,
+    /// The record's original form, if it was parsed. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Option<RawLine>,
 }
 
 /// Represents a test via in the decoded PCB file.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TestVia {
     /// The name of the test via.
     pub testvia: String,
@@ -133,10 +324,119 @@ pub struct TestVia {
     pub test_point: String,
     /// The radius of the test via on the PCB, in [Content::units] units.
     pub radius: Decimal,
+    /// The record's original form, if it was parsed. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Option<RawLine>,
+}
+
+/// Represents a via in the decoded PCB file.
+///
+/// The layout of the `VIAID` section differs between files, so its columns
+/// are matched by title. Columns that don't map to any of these fields are
+/// kept in [Via::extra].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Via {
+    /// The ID of the via.
+    pub via_id: String,
+    /// The name of the net this via is connected to.
+    pub net_name: String,
+    /// The X-coordinate of the via on the PCB, in [Content::units] units.
+    pub via_x: Decimal,
+    /// The Y-coordinate of the via on the PCB, in [Content::units] units.
+    pub via_y: Decimal,
+    /// The radius of the via on the PCB, in [Content::units] units, if the
+    /// file gives one.
+    pub radius: Option<Decimal>,
+    /// The name of the layer the via starts on, if given.
+    pub start_layer: String,
+    /// The name of the layer the via ends on, if given.
+    pub end_layer: String,
+    /// Values of columns that don't map to any of the fields above, as
+    /// `(column title, value)` pairs.
+    pub extra: Vec<(String, String)>,
+    /// The record's original form, if it was parsed. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Option<RawLine>,
+}
+
+/// The meaning of a column in the `VIAID` section.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ViaColumn {
+    Id,
+    NetName,
+    X,
+    Y,
+    Radius,
+    StartLayer,
+    EndLayer,
+}
+
+fn via_column(title: &str) -> Option<ViaColumn> {
+    match title.to_ascii_uppercase().as_str() {
+        "VIAID" | "VIA_ID" => Some(ViaColumn::Id),
+        "NET_NAME" | "NETNAME" => Some(ViaColumn::NetName),
+        "VIA_X" | "X" => Some(ViaColumn::X),
+        "VIA_Y" | "Y" => Some(ViaColumn::Y),
+        "RADIUS" | "VIA_RADIUS" => Some(ViaColumn::Radius),
+        "START_LAYER" | "LAYER_START" | "FROM_LAYER" => Some(ViaColumn::StartLayer),
+        "END_LAYER" | "LAYER_END" | "TO_LAYER" => Some(ViaColumn::EndLayer),
+        _ => None,
+    }
+}
+
+impl Via {
+    /// Builds a via from the fields of an `S` record, given the fields of
+    /// its section's `A` line.
+    fn from_fields(
+        columns: &[String],
+        fields: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut via = Via {
+            via_id: String::new(),
+            net_name: String::new(),
+            via_x: Decimal::ZERO,
+            via_y: Decimal::ZERO,
+            radius: None,
+            start_layer: String::new(),
+            end_layer: String::new(),
+            extra: Vec::new(),
+            raw: None,
+        };
+
+        for (i, value) in fields.iter().enumerate().skip(1) {
+            let title = columns.get(i).map(String::as_str).unwrap_or_default();
+            match via_column(title) {
+                Some(ViaColumn::Id) => via.via_id = value.clone(),
+                Some(ViaColumn::NetName) => via.net_name = value.clone(),
+                Some(ViaColumn::X) => via.via_x = parse_via_decimal(title, value)?,
+                Some(ViaColumn::Y) => via.via_y = parse_via_decimal(title, value)?,
+                Some(ViaColumn::Radius) if !value.is_empty() => {
+                    via.radius = Some(parse_via_decimal(title, value)?)
+                }
+                Some(ViaColumn::Radius) => (),
+                Some(ViaColumn::StartLayer) => via.start_layer = value.clone(),
+                Some(ViaColumn::EndLayer) => via.end_layer = value.clone(),
+                None => {
+                    if !value.is_empty() {
+                        via.extra.push((title.to_string(), value.clone()));
+                    }
+                }
+            }
+        }
+
+        Ok(via)
+    }
+}
+
+fn parse_via_decimal(title: &str, value: &str) -> Result<Decimal, Box<dyn std::error::Error>> {
+    parse_decimal(value.as_bytes())
+        .map_err(|e| format!("Invalid {} {:?}: {}", title, value, e).into())
 }
 
 /// Represents a graphic data entry in the decoded PCB file.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GraphicData {
     pub graphic_data_name: String,
     pub graphic_data_number: u64,
@@ -145,10 +445,14 @@ pub struct GraphicData {
     pub subclass: String,
     pub sym_name: String,
     pub refdes: String,
+    /// The record's original form, if it was parsed. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Option<RawLine>,
 }
 
 /// Represents a classed graphic data entry in the decoded PCB file.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClassedGraphicData {
     pub class: String,
     pub subclass: String,
@@ -157,10 +461,193 @@ pub struct ClassedGraphicData {
     pub record_tag: String,
     pub graphic_data: [String; 9],
     pub net_name: String,
+    /// The record's original form, if it was parsed. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Option<RawLine>,
+}
+
+/// The kind of records a [Section] holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SectionKind {
+    /// The `A!UNIT!` line.
+    Unit,
+    /// Records stored in [Content::symbols].
+    Symbols,
+    /// Records stored in [Content::pins].
+    Pins,
+    /// Records stored in [Content::testvias].
+    TestVias,
+    /// Records stored in [Content::vias].
+    Vias,
+    /// Records stored in [Content::graphic_data].
+    GraphicData,
+    /// Records stored in [Content::classed_graphic_data].
+    ClassedGraphicData,
+    /// A line this parser doesn't interpret, kept as it is in
+    /// [Section::header].
+    Other,
+}
+
+impl SectionKind {
+    const RECORDS: [SectionKind; 6] = [
+        SectionKind::Symbols,
+        SectionKind::Pins,
+        SectionKind::Vias,
+        SectionKind::TestVias,
+        SectionKind::GraphicData,
+        SectionKind::ClassedGraphicData,
+    ];
+
+    /// The header line written for records that have no section of their own.
+    fn default_header(self) -> &'static str {
+        match self {
+            SectionKind::Unit => "A!UNIT!mils!",
+            SectionKind::Symbols => "A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!",
+            SectionKind::Pins => {
+                "A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!"
+            }
+            SectionKind::Vias => "A!VIAID!NET_NAME!VIA_X!VIA_Y!RADIUS!START_LAYER!END_LAYER!",
+            SectionKind::TestVias => {
+                "A!TESTVIA!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!VIA_X!VIA_Y!TEST_POINT!RADIUS!"
+            }
+            SectionKind::GraphicData => {
+                "A!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!GRAPHIC_DATA_1!\
+                 GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!GRAPHIC_DATA_6!\
+                 GRAPHIC_DATA_7!GRAPHIC_DATA_8!GRAPHIC_DATA_9!SUBCLASS!SYM_NAME!REFDES!"
+            }
+            SectionKind::ClassedGraphicData => {
+                "A!CLASS!SUBCLASS!GRAPHIC_DATA_NAME!GRAPHIC_DATA_NUMBER!RECORD_TAG!\
+                 GRAPHIC_DATA_1!GRAPHIC_DATA_2!GRAPHIC_DATA_3!GRAPHIC_DATA_4!GRAPHIC_DATA_5!\
+                 GRAPHIC_DATA_6!GRAPHIC_DATA_7!GRAPHIC_DATA_8!GRAPHIC_DATA_9!NET_NAME!"
+            }
+            SectionKind::Other => "",
+        }
+    }
+}
+
+/// The original bytes of a field that wouldn't be reproduced by encoding its
+/// text, such as text that didn't decode cleanly.
+#[derive(Clone, Debug)]
+struct Verbatim {
+    /// The text the field is written as. The bytes are only written back
+    /// while the field would still be written as this text.
+    text: String,
+    /// The original bytes of the field.
+    bytes: Vec<u8>,
+}
+
+/// The original form of a parsed line, kept with the record or section it
+/// was parsed into so that the line can be written back as it was.
+///
+/// Fields keep their original bytes while their value would still be
+/// written as the text it was parsed as, such as "+5" for 5, or text that
+/// didn't decode cleanly. The bytes are only meaningful for the file they
+/// came from, so they aren't serialized.
+#[derive(Clone, Debug, Default)]
+pub struct RawLine {
+    /// The number of fields on the line, including trailing empty ones.
+    len: usize,
+    /// The fields that wouldn't be written as they were, by field index.
+    verbatim: BTreeMap<usize, Verbatim>,
+}
+
+impl RawLine {
+    /// Records the fields of a line that encoding `written`, the text the
+    /// line would be written as, wouldn't reproduce.
+    fn new<'a>(
+        encoding: TextEncoding,
+        fields: impl IntoIterator<Item = &'a [u8]>,
+        written: &[String],
+    ) -> Self {
+        let mut raw = Self::default();
+        for (i, bytes) in fields.into_iter().enumerate() {
+            let text = written.get(i).cloned().unwrap_or_default();
+            if encoding.encode(&text) != bytes {
+                let bytes = bytes.to_vec();
+                raw.verbatim.insert(i, Verbatim { text, bytes });
+            }
+            raw.len = i + 1;
+        }
+        raw
+    }
+}
+
+/// Encodes a line's fields joined by `delimiter`. If the line was parsed,
+/// fields whose text is unchanged are written from their original bytes, and
+/// the line keeps at least as many fields as it had.
+fn encode_line(
+    encoding: TextEncoding,
+    fields: &[String],
+    delimiter: u8,
+    raw: Option<&RawLine>,
+) -> Vec<u8> {
+    let len = match raw {
+        Some(raw) => fields
+            .iter()
+            .rposition(|f| !f.is_empty())
+            .map_or(0, |i| i + 1)
+            .max(raw.len),
+        None => fields.len(),
+    };
+    let mut line = Vec::new();
+    for i in 0..len {
+        if i > 0 {
+            line.push(delimiter);
+        }
+        let text = fields.get(i).map_or("", String::as_str);
+        match raw.and_then(|raw| raw.verbatim.get(&i)) {
+            Some(v) if v.text == text => line.extend_from_slice(&v.bytes),
+            _ => line.extend(encoding.encode(text)),
+        }
+    }
+    line
+}
+
+/// A line of the content that starts or continues a run of records, or that
+/// isn't part of one, kept so that [Content::write_to] can reproduce the
+/// original layout.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Section {
+    /// The kind of records in this section.
+    pub kind: SectionKind,
+    /// The fields of the section's `A` line, or of the line itself for
+    /// [SectionKind::Unit] and [SectionKind::Other]. Empty if this section
+    /// continues the previous section of the same kind after an
+    /// interruption, such as an `A!LOGOInfo!` line.
+    pub header: Vec<String>,
+    /// The number of records this section held when parsed.
+    pub len: usize,
+    /// The original form of the header line. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raw: Option<RawLine>,
+}
+
+impl Section {
+    fn new(kind: SectionKind, header: Vec<String>) -> Self {
+        Self {
+            kind,
+            header,
+            len: 0,
+            raw: None,
+        }
+    }
+
+    /// Makes a section headed by a parsed line.
+    fn from_line(kind: SectionKind, encoding: TextEncoding, record: &csv::ByteRecord) -> Self {
+        let header: Vec<String> = record.iter().map(|f| encoding.decode(f)).collect();
+        let raw = RawLine::new(encoding, record, &header);
+        Self {
+            raw: Some(raw),
+            ..Self::new(kind, header)
+        }
+    }
 }
 
 /// Parsed content of the decoded PCB file.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Content {
     /// The unit system used in the file.
     pub units: Units,
@@ -168,16 +655,30 @@ pub struct Content {
     pub symbols: Vec<Symbol>,
     /// List of pins in the file.
     pub pins: Vec<Pin>,
+    /// List of vias in the file.
+    pub vias: Vec<Via>,
     /// List of test vias in the file.
     pub testvias: Vec<TestVia>,
     /// List of graphic data entries.
     pub graphic_data: Vec<GraphicData>,
     /// List of classed graphic data entries.
     pub classed_graphic_data: Vec<ClassedGraphicData>,
+    /// The sections of the file, in file order.
+    pub sections: Vec<Section>,
+    /// Records that were skipped because they couldn't be understood. They
+    /// are kept as [SectionKind::Other] sections.
+    pub warnings: Vec<ParseWarning>,
+    /// The line terminator used in the file.
+    pub line_ending: LineEnding,
+    /// The encoding the text fields were decoded from. Fields that didn't
+    /// decode cleanly keep their original bytes in their record's
+    /// [RawLine], and are written back unchanged by [Content::write_to].
+    pub encoding: TextEncoding,
 }
 
 impl Content {
-    /// Parses the decoded content into structured data.
+    /// Parses the decoded content into structured data, detecting the text
+    /// encoding with [TextEncoding::detect].
     ///
     /// # Arguments
     ///
@@ -187,34 +688,65 @@ impl Content {
     ///
     /// A `Result` containing the parsed `Content` or an error.
     pub fn from_bytes(content: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
-        let mut symbols = Vec::new();
-        let mut pins = Vec::new();
-        let mut testvias = Vec::new();
-        let mut graphic_data = Vec::new();
-        let mut classed_graphic_data = Vec::new();
+        Self::from_bytes_with_encoding(content, TextEncoding::detect(content))
+    }
 
-        let mut reader = csv::ReaderBuilder::new()
-            .delimiter(b'!')
-            .flexible(true)
-            .has_headers(false)
-            .from_reader(content);
+    /// Parses the decoded content into structured data, decoding text fields
+    /// with the given encoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The raw decoded bytes from the file.
+    /// * `encoding` - The encoding of the text fields.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Content` or an error.
+    pub fn from_bytes_with_encoding(
+        content: &[u8],
+        encoding: TextEncoding,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut parsed = Self {
+            units: Units::Mils,
+            symbols: Vec::new(),
+            pins: Vec::new(),
+            vias: Vec::new(),
+            testvias: Vec::new(),
+            graphic_data: Vec::new(),
+            classed_graphic_data: Vec::new(),
+            sections: Vec::new(),
+            warnings: Vec::new(),
+            line_ending: LineEnding::detect(content),
+            encoding,
+        };
+
+        // Lines are split up here rather than by a CSV reader so that blank
+        // lines are kept
+        let mut lines: Vec<&[u8]> = content.split(|&b| b == b'\n').collect();
+        if lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
 
         let mut state = ParserState::Unknown;
-        let mut units = Units::Mils;
+        let mut via_columns = Vec::new();
+        // Set after a line that interrupts a section without ending it.
+        let mut interrupted = true;
 
-        for result in reader.byte_records() {
-            let record = result?;
-            if record.is_empty() {
-                continue;
-            }
+        for (i, bytes) in lines.into_iter().enumerate() {
+            let line = i as u64 + 1;
+            let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+            let record: csv::ByteRecord = bytes.split(|&b| b == b'!').collect();
 
             let first = &record[0];
             if first == b"A" {
+                if record.len() < 2 {
+                    return Err(format!("Line {}: \"A\" line has no title", line).into());
+                }
                 if &record[1] == b"UNIT" {
-                    if &record[2] == b"mils" {
-                        units = Units::Mils;
+                    if record.get(2) == Some(&b"mils"[..]) {
+                        parsed.units = Units::Mils;
                     } else {
-                        units = Units::Millimeters;
+                        parsed.units = Units::Millimeters;
                     }
                 } else if &record[1] == b"REFDES" {
                     state = ParserState::Symbol;
@@ -222,6 +754,7 @@ impl Content {
                     state = ParserState::Pin;
                 } else if &record[1] == b"VIAID" {
                     state = ParserState::Via;
+                    via_columns = record.iter().map(|f| encoding.decode(f)).collect();
                 } else if &record[1] == b"TESTVIA" {
                     state = ParserState::TestVia;
                 } else if &record[1] == b"GRAPHIC_DATA_NAME" {
@@ -233,125 +766,418 @@ impl Content {
                 } else {
                     state = ParserState::Unknown;
                 }
-            } else if first == b"S" {
-                match state {
-                    ParserState::Symbol => {
-                        symbols.push(Symbol {
-                            refdes: String::from_utf8_lossy(&record[1]).to_string(),
-                            comp_insertion_code: String::from_utf8_lossy(&record[2])
-                                .to_string()
-                                .parse::<u64>()?,
-                            sym_name: String::from_utf8_lossy(&record[3]).to_string(),
-                            sym_mirror: &record[4] == b"YES",
-                            sym_rotate: String::from_utf8_lossy(&record[5])
-                                .to_string()
-                                .parse::<u16>()?,
-                        });
-                    }
-                    ParserState::Pin => {
-                        pins.push(Pin {
-                            net_name: String::from_utf8_lossy(&record[1]).to_string(),
-                            refdes: String::from_utf8_lossy(&record[2]).to_string(),
-                            pin_number: String::from_utf8_lossy(&record[3]).to_string(),
-                            pin_name: String::from_utf8_lossy(&record[4]).to_string(),
-                            pin_x: parse_decimal(&record[5])?,
-                            pin_y: parse_decimal(&record[6])?,
-                            test_point: String::from_utf8_lossy(&record[7]).to_string(),
-                            radius: parse_decimal(&record[8])?,
-                        });
-                    }
-                    ParserState::TestVia => {
-                        testvias.push(TestVia {
-                            testvia: String::from_utf8_lossy(&record[1]).to_string(),
-                            net_name: String::from_utf8_lossy(&record[2]).to_string(),
-                            refdes: String::from_utf8_lossy(&record[3]).to_string(),
-                            pin_number: String::from_utf8_lossy(&record[4]).to_string(),
-                            pin_name: String::from_utf8_lossy(&record[5]).to_string(),
-                            via_x: parse_decimal(&record[6])?,
-                            via_y: parse_decimal(&record[7])?,
-                            test_point: String::from_utf8_lossy(&record[8]).to_string(),
-                            radius: parse_decimal(&record[9])?,
-                        });
-                    }
-                    ParserState::GraphicData => {
-                        graphic_data.push(GraphicData {
-                            graphic_data_name: String::from_utf8_lossy(&record[1]).to_string(),
-                            graphic_data_number: String::from_utf8_lossy(&record[2])
-                                .to_string()
-                                .parse::<u64>()?,
-                            record_tag: String::from_utf8_lossy(&record[3]).to_string(),
-                            graphic_data: [
-                                String::from_utf8_lossy(&record[4]).to_string(),
-                                String::from_utf8_lossy(&record[5]).to_string(),
-                                String::from_utf8_lossy(&record[6]).to_string(),
-                                String::from_utf8_lossy(&record[7]).to_string(),
-                                String::from_utf8_lossy(&record[8]).to_string(),
-                                String::from_utf8_lossy(&record[9]).to_string(),
-                                String::from_utf8_lossy(&record[10]).to_string(),
-                                String::from_utf8_lossy(&record[11]).to_string(),
-                                String::from_utf8_lossy(&record[12]).to_string(),
-                            ],
-                            subclass: String::from_utf8_lossy(&record[13]).to_string(),
-                            sym_name: String::from_utf8_lossy(&record[14]).to_string(),
-                            refdes: String::from_utf8_lossy(&record[15]).to_string(),
-                        });
-                    }
-                    ParserState::ClassedGraphicData => {
-                        classed_graphic_data.push(ClassedGraphicData {
-                            class: String::from_utf8_lossy(&record[1]).to_string(),
-                            subclass: String::from_utf8_lossy(&record[2]).to_string(),
-                            graphic_data_name: String::from_utf8_lossy(&record[3]).to_string(),
-                            graphic_data_number: String::from_utf8_lossy(&record[4])
-                                .to_string()
-                                .parse::<u64>()?,
-                            record_tag: String::from_utf8_lossy(&record[5]).to_string(),
-                            graphic_data: [
-                                String::from_utf8_lossy(&record[6]).to_string(),
-                                String::from_utf8_lossy(&record[7]).to_string(),
-                                String::from_utf8_lossy(&record[8]).to_string(),
-                                String::from_utf8_lossy(&record[9]).to_string(),
-                                String::from_utf8_lossy(&record[10]).to_string(),
-                                String::from_utf8_lossy(&record[11]).to_string(),
-                                String::from_utf8_lossy(&record[12]).to_string(),
-                                String::from_utf8_lossy(&record[13]).to_string(),
-                                String::from_utf8_lossy(&record[14]).to_string(),
-                            ],
-                            net_name: String::from_utf8_lossy(&record[15]).to_string(),
-                        });
+                let kind = match &record[1] {
+                    b"UNIT" => SectionKind::Unit,
+                    // These interrupt a section without ending it
+                    b"LOGOInfo" | b"UnDrawSym" => SectionKind::Other,
+                    _ => state.section_kind(),
+                };
+                interrupted = !SectionKind::RECORDS.contains(&kind);
+                parsed
+                    .sections
+                    .push(Section::from_line(kind, encoding, &record));
+                continue;
+            } else if first != b"S" || matches!(state, ParserState::Unknown) {
+                parsed
+                    .sections
+                    .push(Section::from_line(SectionKind::Other, encoding, &record));
+                interrupted = true;
+                continue;
+            }
+
+            if record.len() < state.min_fields() {
+                return Err(format!(
+                    "Line {}: record has {} of {} fields",
+                    line,
+                    record.len(),
+                    state.min_fields()
+                )
+                .into());
+            }
+
+            let via = match state {
+                ParserState::Via => {
+                    let fields: Vec<String> = record.iter().map(|f| encoding.decode(f)).collect();
+                    match Via::from_fields(&via_columns, &fields) {
+                        Ok(via) => Some(via),
+                        Err(e) => {
+                            parsed.warnings.push(ParseWarning {
+                                line,
+                                message: format!("Skipped via: {}", e),
+                            });
+                            parsed.sections.push(Section::from_line(
+                                SectionKind::Other,
+                                encoding,
+                                &record,
+                            ));
+                            interrupted = true;
+                            continue;
+                        }
                     }
-                    _ => (),
                 }
+                _ => None,
+            };
+
+            if interrupted {
+                let section = Section::new(state.section_kind(), Vec::new());
+                parsed.sections.push(section);
+                interrupted = false;
+            }
+
+            match state {
+                ParserState::Symbol => {
+                    parsed.symbols.push(Symbol {
+                        refdes: encoding.decode(&record[1]),
+                        comp_insertion_code: String::from_utf8_lossy(&record[2])
+                            .to_string()
+                            .parse::<u64>()?,
+                        sym_name: encoding.decode(&record[3]),
+                        sym_mirror: &record[4] == b"YES",
+                        sym_rotate: String::from_utf8_lossy(&record[5])
+                            .to_string()
+                            .parse::<u16>()?,
+                        raw: None,
+                    });
+                }
+                ParserState::Pin => {
+                    parsed.pins.push(Pin {
+                        net_name: encoding.decode(&record[1]),
+                        refdes: encoding.decode(&record[2]),
+                        pin_number: encoding.decode(&record[3]),
+                        pin_name: encoding.decode(&record[4]),
+                        pin_x: parse_decimal(&record[5])?,
+                        pin_y: parse_decimal(&record[6])?,
+                        test_point: encoding.decode(&record[7]),
+                        radius: parse_decimal(&record[8])?,
+                        raw: None,
+                    });
+                }
+                ParserState::Via => parsed.vias.extend(via),
+                ParserState::TestVia => {
+                    parsed.testvias.push(TestVia {
+                        testvia: encoding.decode(&record[1]),
+                        net_name: encoding.decode(&record[2]),
+                        refdes: encoding.decode(&record[3]),
+                        pin_number: encoding.decode(&record[4]),
+                        pin_name: encoding.decode(&record[5]),
+                        via_x: parse_decimal(&record[6])?,
+                        via_y: parse_decimal(&record[7])?,
+                        test_point: encoding.decode(&record[8]),
+                        radius: parse_decimal(&record[9])?,
+                        raw: None,
+                    });
+                }
+                ParserState::GraphicData => {
+                    parsed.graphic_data.push(GraphicData {
+                        graphic_data_name: encoding.decode(&record[1]),
+                        graphic_data_number: String::from_utf8_lossy(&record[2])
+                            .to_string()
+                            .parse::<u64>()?,
+                        record_tag: encoding.decode(&record[3]),
+                        graphic_data: [
+                            encoding.decode(&record[4]),
+                            encoding.decode(&record[5]),
+                            encoding.decode(&record[6]),
+                            encoding.decode(&record[7]),
+                            encoding.decode(&record[8]),
+                            encoding.decode(&record[9]),
+                            encoding.decode(&record[10]),
+                            encoding.decode(&record[11]),
+                            encoding.decode(&record[12]),
+                        ],
+                        subclass: encoding.decode(&record[13]),
+                        sym_name: encoding.decode(&record[14]),
+                        refdes: encoding.decode(&record[15]),
+                        raw: None,
+                    });
+                }
+                ParserState::ClassedGraphicData => {
+                    parsed.classed_graphic_data.push(ClassedGraphicData {
+                        class: encoding.decode(&record[1]),
+                        subclass: encoding.decode(&record[2]),
+                        graphic_data_name: encoding.decode(&record[3]),
+                        graphic_data_number: String::from_utf8_lossy(&record[4])
+                            .to_string()
+                            .parse::<u64>()?,
+                        record_tag: encoding.decode(&record[5]),
+                        graphic_data: [
+                            encoding.decode(&record[6]),
+                            encoding.decode(&record[7]),
+                            encoding.decode(&record[8]),
+                            encoding.decode(&record[9]),
+                            encoding.decode(&record[10]),
+                            encoding.decode(&record[11]),
+                            encoding.decode(&record[12]),
+                            encoding.decode(&record[13]),
+                            encoding.decode(&record[14]),
+                        ],
+                        net_name: encoding.decode(&record[15]),
+                        raw: None,
+                    });
+                }
+                ParserState::Unknown => (),
+            }
+
+            // Keep the fields that wouldn't be written as they were with the
+            // record itself
+            let kind = state.section_kind();
+            let index = parsed.record_count(kind) - 1;
+            let (written, _) = parsed.record(kind, index, &via_columns);
+            let raw = Some(RawLine::new(encoding, &record, &written));
+            match kind {
+                SectionKind::Symbols => parsed.symbols[index].raw = raw,
+                SectionKind::Pins => parsed.pins[index].raw = raw,
+                SectionKind::Vias => parsed.vias[index].raw = raw,
+                SectionKind::TestVias => parsed.testvias[index].raw = raw,
+                SectionKind::GraphicData => parsed.graphic_data[index].raw = raw,
+                SectionKind::ClassedGraphicData => parsed.classed_graphic_data[index].raw = raw,
+                SectionKind::Unit | SectionKind::Other => (),
+            }
+            if let Some(section) = parsed.sections.last_mut() {
+                section.len += 1;
             }
         }
 
-        Ok(Self {
-            units,
-            symbols,
-            pins,
-            testvias,
-            graphic_data,
-            classed_graphic_data,
-        })
+        Ok(parsed)
+    }
+
+    /// Serializes the content back into the `!`-delimited text format.
+    ///
+    /// Sections are written in the order given by [Content::sections], with
+    /// each known section taking as many records as it held when parsed and
+    /// the last section of each kind taking any remaining ones. Records with
+    /// no section of their own are written after a default header at the
+    /// end. Parsed records are written back from their [RawLine], so that
+    /// unmodified content is reproduced byte-for-byte: fields whose text
+    /// differs from how their value would be written, such as "+5" for 5 or
+    /// "no" for an unmirrored symbol, keep that text while their value is
+    /// unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the content to.
+    pub fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        let mut sections: Vec<&Section> = self.sections.iter().collect();
+        let defaults: Vec<Section> = std::iter::once(SectionKind::Unit)
+            .filter(|_| self.sections.is_empty())
+            .chain(SectionKind::RECORDS)
+            .filter(|&kind| self.sections.is_empty() || self.record_count(kind) > 0)
+            .filter(|&kind| sections.iter().all(|s| s.kind != kind))
+            .map(|kind| {
+                let header = kind.default_header().split('!').map(String::from).collect();
+                Section::new(kind, header)
+            })
+            .collect();
+        sections.extend(defaults.iter());
+
+        let mut next = [0usize; SectionKind::RECORDS.len()];
+        let mut columns: [&[String]; SectionKind::RECORDS.len()] = Default::default();
+        for (i, section) in sections.iter().enumerate() {
+            if section.kind == SectionKind::Unit {
+                let header = match (&self.units, section.header.get(2).map(String::as_str)) {
+                    (Units::Mils, Some("mils")) => section.header.clone(),
+                    (Units::Millimeters, Some(unit)) if unit != "mils" => section.header.clone(),
+                    (Units::Mils, _) => vec!["A".into(), "UNIT".into(), "mils".into(), "".into()],
+                    (Units::Millimeters, _) => {
+                        vec!["A".into(), "UNIT".into(), "millimeters".into(), "".into()]
+                    }
+                };
+                self.write_line(writer, &header, section.raw.as_ref())?;
+                continue;
+            }
+
+            if !section.header.is_empty() {
+                self.write_line(writer, &section.header, section.raw.as_ref())?;
+            }
+
+            let Some(k) = SectionKind::RECORDS.iter().position(|&k| k == section.kind) else {
+                continue;
+            };
+
+            if !section.header.is_empty() {
+                columns[k] = &section.header;
+            }
+            let total = self.record_count(section.kind);
+            let is_last = sections[i + 1..].iter().all(|s| s.kind != section.kind);
+            let end = if is_last {
+                total
+            } else {
+                (next[k] + section.len).min(total)
+            };
+            for index in next[k]..end {
+                let (fields, raw) = self.record(section.kind, index, columns[k]);
+                self.write_line(writer, &fields, raw)?;
+            }
+            next[k] = end;
+        }
+
+        Ok(())
+    }
+
+    fn record_count(&self, kind: SectionKind) -> usize {
+        match kind {
+            SectionKind::Symbols => self.symbols.len(),
+            SectionKind::Pins => self.pins.len(),
+            SectionKind::Vias => self.vias.len(),
+            SectionKind::TestVias => self.testvias.len(),
+            SectionKind::GraphicData => self.graphic_data.len(),
+            SectionKind::ClassedGraphicData => self.classed_graphic_data.len(),
+            SectionKind::Unit | SectionKind::Other => 0,
+        }
+    }
+
+    /// Returns the fields a record is written as, ending with an empty field
+    /// for the line's trailing `!`, along with its original form.
+    fn record(
+        &self,
+        kind: SectionKind,
+        index: usize,
+        columns: &[String],
+    ) -> (Vec<String>, Option<&RawLine>) {
+        let mut fields = vec!["S".to_string()];
+        let raw = match kind {
+            SectionKind::Symbols => {
+                let symbol = &self.symbols[index];
+                fields.extend([
+                    symbol.refdes.clone(),
+                    symbol.comp_insertion_code.to_string(),
+                    symbol.sym_name.clone(),
+                    if symbol.sym_mirror { "YES" } else { "NO" }.to_string(),
+                    symbol.sym_rotate.to_string(),
+                ]);
+                &symbol.raw
+            }
+            SectionKind::Pins => {
+                let pin = &self.pins[index];
+                fields.extend([
+                    pin.net_name.clone(),
+                    pin.refdes.clone(),
+                    pin.pin_number.clone(),
+                    pin.pin_name.clone(),
+                    pin.pin_x.to_string(),
+                    pin.pin_y.to_string(),
+                    pin.test_point.clone(),
+                    pin.radius.to_string(),
+                ]);
+                &pin.raw
+            }
+            SectionKind::Vias => {
+                let via = &self.vias[index];
+                fields.extend(columns.iter().skip(1).map(|title| {
+                    match via_column(title) {
+                        Some(ViaColumn::Id) => via.via_id.clone(),
+                        Some(ViaColumn::NetName) => via.net_name.clone(),
+                        Some(ViaColumn::X) => via.via_x.to_string(),
+                        Some(ViaColumn::Y) => via.via_y.to_string(),
+                        Some(ViaColumn::Radius) => {
+                            via.radius.map(|r| r.to_string()).unwrap_or_default()
+                        }
+                        Some(ViaColumn::StartLayer) => via.start_layer.clone(),
+                        Some(ViaColumn::EndLayer) => via.end_layer.clone(),
+                        None => via
+                            .extra
+                            .iter()
+                            .find(|(t, _)| t == title)
+                            .map(|(_, v)| v.clone())
+                            .unwrap_or_default(),
+                    }
+                }));
+                // The columns already end with the empty title after the
+                // header's trailing `!`
+                return (fields, via.raw.as_ref());
+            }
+            SectionKind::TestVias => {
+                let testvia = &self.testvias[index];
+                fields.extend([
+                    testvia.testvia.clone(),
+                    testvia.net_name.clone(),
+                    testvia.refdes.clone(),
+                    testvia.pin_number.clone(),
+                    testvia.pin_name.clone(),
+                    testvia.via_x.to_string(),
+                    testvia.via_y.to_string(),
+                    testvia.test_point.clone(),
+                    testvia.radius.to_string(),
+                ]);
+                &testvia.raw
+            }
+            SectionKind::GraphicData => {
+                let gd = &self.graphic_data[index];
+                fields.extend([
+                    gd.graphic_data_name.clone(),
+                    gd.graphic_data_number.to_string(),
+                    gd.record_tag.clone(),
+                ]);
+                fields.extend(gd.graphic_data.iter().cloned());
+                fields.extend([gd.subclass.clone(), gd.sym_name.clone(), gd.refdes.clone()]);
+                &gd.raw
+            }
+            SectionKind::ClassedGraphicData => {
+                let cgd = &self.classed_graphic_data[index];
+                fields.extend([
+                    cgd.class.clone(),
+                    cgd.subclass.clone(),
+                    cgd.graphic_data_name.clone(),
+                    cgd.graphic_data_number.to_string(),
+                    cgd.record_tag.clone(),
+                ]);
+                fields.extend(cgd.graphic_data.iter().cloned());
+                fields.push(cgd.net_name.clone());
+                &cgd.raw
+            }
+            SectionKind::Unit | SectionKind::Other => &None,
+        };
+        fields.push(String::new());
+        (fields, raw.as_ref())
+    }
+
+    /// Writes one `!`-delimited line.
+    fn write_line(
+        &self,
+        writer: &mut impl Write,
+        fields: &[String],
+        raw: Option<&RawLine>,
+    ) -> std::io::Result<()> {
+        writer.write_all(&encode_line(self.encoding, fields, b'!', raw))?;
+        writer.write_all(self.line_ending.as_bytes())
     }
 }
 
+/// A non-fatal problem encountered while parsing.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseWarning {
+    /// The 1-based line number the problem was found on.
+    pub line: u64,
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
 /// Represents a component in the decoded PCB file's description.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Component {
     /// The part number of the component.
     pub part_number: String,
     /// The description/name of the component.
     pub description: String,
-    /// The number of times this component is used on the PCB.
-    pub quantity: u64,
+    /// The number of times this component is used on the PCB, if known.
+    pub quantity: Option<u64>,
     /// List of reference designators on the PCB where this component is used.
     pub location: Vec<String>,
     /// An alternate part number.
     pub part_number2: String,
+    /// Non-empty values of columns that don't map to any of the fields
+    /// above, by column index. The titles are in [Description::columns].
+    pub extra: BTreeMap<usize, String>,
+    /// The row's original form, if it was parsed. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub raw: Option<RawLine>,
 }
 
 /// The PCB file's description information.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Description {
     /// PCB model number.
     pub board_model: String,
@@ -363,55 +1189,305 @@ pub struct Description {
     pub extended_revision: String,
     /// Part number of the PCB.
     pub part_number: String,
+    /// Any header fields after the part number.
+    pub header_extra: Vec<String>,
+    /// The column titles of the component table, in file order. Empty if the
+    /// file has no recognizable column-title row.
+    pub columns: Vec<String>,
     /// List of components on the PCB.
     pub components: Vec<Component>,
+    /// Rows that were skipped or only partially understood.
+    pub warnings: Vec<ParseWarning>,
+    /// The encoding the text fields were decoded from. Fields that didn't
+    /// decode cleanly keep their original bytes, and are written back
+    /// unchanged by [Description::write_to].
+    pub encoding: TextEncoding,
+    /// The original form of the header line. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raw_header: Option<RawLine>,
+    /// The original form of the column-title row. Not serialized.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    raw_columns: Option<RawLine>,
 }
 
-impl Description {
-    pub fn from_bytes(description: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
-        let description_str = String::from_utf8_lossy(description);
-        let lines = description_str.split("\r\n").collect::<Vec<_>>();
-        if lines.is_empty() {
-            return Err("Description is empty".into());
+/// The meaning of a column in the description's component table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColumnKind {
+    PartNumber,
+    Description,
+    Quantity,
+    Location,
+    PartNumber2,
+    Other,
+}
+
+/// The column order used by files without a column-title row.
+const DEFAULT_COLUMNS: [ColumnKind; 5] = [
+    ColumnKind::PartNumber,
+    ColumnKind::Description,
+    ColumnKind::Quantity,
+    ColumnKind::Location,
+    ColumnKind::PartNumber2,
+];
+
+fn column_kind(title: &str) -> Option<ColumnKind> {
+    let normalized: String = title
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_uppercase())
+        .collect();
+    match normalized.as_str() {
+        "PARTNUMBER" | "PARTNO" | "PN" | "PARTNUM" | "MATERIAL" | "MATERIALNO" => {
+            Some(ColumnKind::PartNumber)
+        }
+        "PARTNUMBER2" | "PARTNO2" | "PN2" | "ALTPARTNUMBER" | "SUBSTITUTE" => {
+            Some(ColumnKind::PartNumber2)
         }
+        "QTY" | "QUANTITY" | "QTYPCS" | "COUNT" => Some(ColumnKind::Quantity),
+        "LOCATION" | "LOCATIONS" | "REFDES" | "REFERENCE" | "REFERENCES" | "DESIGNATOR"
+        | "DESIGNATORS" => Some(ColumnKind::Location),
+        s if s.starts_with("DESC") || s == "SPEC" || s == "SPECIFICATION" => {
+            Some(ColumnKind::Description)
+        }
+        _ => None,
+    }
+}
 
-        let header = lines[0].split('|').collect::<Vec<_>>();
-        if header.len() < 5 {
-            return Err("Invalid header format".into());
+/// Maps each column title to a [ColumnKind]. The first part number column is
+/// the primary one; any later one is treated as the alternate.
+fn column_kinds<S: AsRef<str>>(titles: &[S]) -> Vec<ColumnKind> {
+    let mut kinds: Vec<ColumnKind> = Vec::with_capacity(titles.len());
+    for title in titles {
+        let kind = match column_kind(title.as_ref()) {
+            Some(ColumnKind::PartNumber) if kinds.contains(&ColumnKind::PartNumber) => {
+                ColumnKind::PartNumber2
+            }
+            Some(kind) if kind != ColumnKind::PartNumber2 && kinds.contains(&kind) => {
+                ColumnKind::Other
+            }
+            Some(kind) => kind,
+            None => ColumnKind::Other,
+        };
+        kinds.push(kind);
+    }
+    kinds
+}
+
+/// Returns the fields of a component's row in a table with the given column
+/// kinds, followed by any extra values past the last column.
+fn component_fields(component: &Component, kinds: &[ColumnKind]) -> Vec<String> {
+    let len = component
+        .extra
+        .keys()
+        .next_back()
+        .map_or(0, |&i| i + 1)
+        .max(kinds.len());
+    (0..len)
+        .map(
+            |i| match kinds.get(i).copied().unwrap_or(ColumnKind::Other) {
+                ColumnKind::PartNumber => component.part_number.clone(),
+                ColumnKind::Description => component.description.clone(),
+                ColumnKind::Quantity => component
+                    .quantity
+                    .map(|q| q.to_string())
+                    .unwrap_or_default(),
+                ColumnKind::Location => component.location.join(" "),
+                ColumnKind::PartNumber2 => component.part_number2.clone(),
+                ColumnKind::Other => component.extra.get(&i).cloned().unwrap_or_default(),
+            },
+        )
+        .collect()
+}
+
+fn parse_quantity(s: &str) -> Result<Option<u64>, ()> {
+    let s = s.trim();
+    if s.is_empty() || s == "-" || s.eq_ignore_ascii_case("N/A") || s.eq_ignore_ascii_case("NA") {
+        return Ok(None);
+    }
+    if let Ok(q) = s.parse::<u64>() {
+        return Ok(Some(q));
+    }
+    // Some tools export whole quantities as decimals (e.g., "2.000").
+    match Decimal::from_str(&s.replace(',', ".")) {
+        Ok(d) if d.fract().is_zero() && !d.is_sign_negative() => {
+            Ok(Some(d.trunc().try_into().map_err(|_| ())?))
         }
+        _ => Err(()),
+    }
+}
 
-        let board_model = header[0].to_string();
-        let revision = header[1].to_string();
-        let extended_board_model = header[2].to_string();
-        let extended_revision = header[3].to_string();
-        let part_number = header[4].to_string();
+impl Description {
+    /// Parses the decoded description into structured data, detecting the
+    /// text encoding with [TextEncoding::detect].
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The raw decoded bytes from the file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Description` or an error.
+    pub fn from_bytes(description: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_bytes_with_encoding(description, TextEncoding::detect(description))
+    }
 
-        let mut component_reader = csv::ReaderBuilder::new()
+    /// Parses the decoded description into structured data, decoding text
+    /// fields with the given encoding.
+    ///
+    /// Both CRLF and LF line endings are accepted. The component table's
+    /// column-title row is located by its contents, and rows that can't be
+    /// understood are recorded in [Description::warnings] rather than failing
+    /// the whole parse.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The raw decoded bytes from the file.
+    /// * `encoding` - The encoding of the text fields.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `Description` or an error.
+    pub fn from_bytes_with_encoding(
+        description: &[u8],
+        encoding: TextEncoding,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut warnings = Vec::new();
+
+        let mut reader = csv::ReaderBuilder::new()
             .delimiter(b'\t')
             .flexible(true)
             .has_headers(false)
+            .quoting(false)
             .from_reader(description);
 
-        let mut components = Vec::new();
-        for result in component_reader.byte_records().skip(2) {
+        let mut rows = Vec::new();
+        let mut records = Vec::new();
+        for result in reader.byte_records() {
             let record = result?;
-            if record.len() < 5 {
-                continue; // Skip malformed lines
-            }
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            let fields: Vec<String> = record.iter().map(|f| encoding.decode(f)).collect();
+            rows.push((line, fields));
+            records.push(record);
+        }
 
-            components.push(Component {
-                part_number: String::from_utf8_lossy(&record[0]).to_string(),
-                description: String::from_utf8_lossy(&record[1]).to_string(),
-                quantity: String::from_utf8_lossy(&record[2])
-                    .to_string()
-                    .parse::<u64>()?,
-                location: String::from_utf8_lossy(&record[3])
-                    .split_whitespace()
-                    .map(String::from)
-                    .collect(),
-                part_number2: String::from_utf8_lossy(&record[4]).to_string(),
+        let Some((header_line, header_fields)) = rows.first() else {
+            return Err("Description is empty".into());
+        };
+        let header_line = *header_line;
+        let header_str = header_fields.join("\t");
+        let header = header_str.split('|').collect::<Vec<_>>();
+        // Multi-byte characters can contain a '|' byte, so the header's
+        // bytes are only split up if they agree with the decoded fields
+        let header_bytes = records[0].iter().collect::<Vec<_>>().join(&b'\t');
+        let header_parts: Vec<&[u8]> = header_bytes.split(|&b| b == b'|').collect();
+        let raw_header = (header_parts.len() == header.len()).then(|| {
+            let written: Vec<String> = header.iter().map(|s| s.to_string()).collect();
+            RawLine::new(encoding, header_parts, &written)
+        });
+        if header.len() < 5 {
+            warnings.push(ParseWarning {
+                line: header_line,
+                message: format!("Header has {} of 5 fields", header.len()),
             });
         }
+        let header_field = |i: usize| header.get(i).map(|s| s.to_string()).unwrap_or_default();
+
+        let board_model = header_field(0);
+        let revision = header_field(1);
+        let extended_board_model = header_field(2);
+        let extended_revision = header_field(3);
+        let part_number = header_field(4);
+        let header_extra = header.iter().skip(5).map(|s| s.to_string()).collect();
+
+        // Find the column-title row. It must identify at least two columns so
+        // that a stray component row isn't mistaken for it.
+        let title_row = rows
+            .iter()
+            .skip(1)
+            .position(|(_, fields)| fields.iter().filter_map(|f| column_kind(f)).count() >= 2);
+
+        let (columns, kinds, data_start) = match title_row {
+            Some(i) => {
+                let columns = rows[i + 1].1.clone();
+                let kinds = column_kinds(&columns);
+                (columns, kinds, i + 2)
+            }
+            None => {
+                warnings.push(ParseWarning {
+                    line: header_line,
+                    message: "No column-title row found, assuming the default layout".into(),
+                });
+                (Vec::new(), DEFAULT_COLUMNS.to_vec(), 2)
+            }
+        };
+
+        let mut components = Vec::new();
+        for ((line, fields), record) in rows.iter().zip(&records).skip(data_start) {
+            if fields.iter().all(|f| f.trim().is_empty()) {
+                continue;
+            }
+
+            let mut component = Component {
+                part_number: String::new(),
+                description: String::new(),
+                quantity: None,
+                location: Vec::new(),
+                part_number2: String::new(),
+                extra: BTreeMap::new(),
+                raw: None,
+            };
+
+            for (i, value) in fields.iter().enumerate() {
+                let kind = kinds.get(i).copied().unwrap_or(ColumnKind::Other);
+                match kind {
+                    ColumnKind::PartNumber => component.part_number = value.clone(),
+                    ColumnKind::Description => component.description = value.clone(),
+                    ColumnKind::Quantity => match parse_quantity(value) {
+                        Ok(quantity) => component.quantity = quantity,
+                        Err(()) => warnings.push(ParseWarning {
+                            line: *line,
+                            message: format!("Invalid quantity {:?}", value),
+                        }),
+                    },
+                    ColumnKind::Location => {
+                        component.location = value
+                            .split(|c: char| c.is_whitespace() || c == ',')
+                            .filter(|s| !s.is_empty())
+                            .map(String::from)
+                            .collect();
+                    }
+                    ColumnKind::PartNumber2 => component.part_number2 = value.clone(),
+                    ColumnKind::Other => {
+                        if !value.is_empty() {
+                            component.extra.insert(i, value.clone());
+                        }
+                    }
+                }
+            }
+
+            if component.part_number.is_empty()
+                && component.description.is_empty()
+                && component.location.is_empty()
+            {
+                warnings.push(ParseWarning {
+                    line: *line,
+                    message: "Row has no part number, description, or location".into(),
+                });
+                continue;
+            }
+            if fields.len() < kinds.len() {
+                warnings.push(ParseWarning {
+                    line: *line,
+                    message: format!("Row has {} of {} columns", fields.len(), kinds.len()),
+                });
+            }
+
+            // Values that wouldn't be written as they were, such as a
+            // quantity of "N/A", keep their original text
+            let written = component_fields(&component, &kinds);
+            component.raw = Some(RawLine::new(encoding, record, &written));
+            components.push(component);
+        }
 
         Ok(Self {
             board_model,
@@ -419,13 +1495,87 @@ impl Description {
             extended_board_model,
             extended_revision,
             part_number,
+            header_extra,
+            columns,
             components,
+            warnings,
+            encoding,
+            raw_header,
+            raw_columns: title_row.map(|i| RawLine::new(encoding, &records[i + 1], &rows[i + 1].1)),
         })
     }
+
+    /// Serializes the description back into its text layout: the
+    /// `|`-separated header line, the column-title row, and one
+    /// tab-separated row per component, each ending in CRLF.
+    ///
+    /// Columns are written in the order of [Description::columns], or in the
+    /// default order if it's empty. Values in [Component::extra] are written
+    /// under the column with their index. Parsed rows are written back from
+    /// their [RawLine], so unmodified fields keep their original text even
+    /// where their value was parsed from different text.
+    ///
+    /// Only the header, the column titles and the components are written.
+    /// Any other rows of the parsed file, such as blank rows, rows before the
+    /// column-title row, and rows skipped with a [ParseWarning], are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the description to.
+    pub fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        let mut header = [
+            self.board_model.as_str(),
+            self.revision.as_str(),
+            self.extended_board_model.as_str(),
+            self.extended_revision.as_str(),
+            self.part_number.as_str(),
+        ]
+        .map(String::from)
+        .to_vec();
+        header.extend(self.header_extra.iter().cloned());
+        self.write_line(writer, &header, b'|', self.raw_header.as_ref())?;
+
+        let default_columns = [
+            "PART NUMBER",
+            "DESCRIPTION",
+            "QTY",
+            "LOCATION",
+            "PART NUMBER2",
+        ];
+        let (titles, kinds) = if self.columns.is_empty() {
+            (
+                default_columns.map(String::from).to_vec(),
+                DEFAULT_COLUMNS.to_vec(),
+            )
+        } else {
+            (self.columns.clone(), column_kinds(&self.columns))
+        };
+        self.write_line(writer, &titles, b'\t', self.raw_columns.as_ref())?;
+
+        for component in &self.components {
+            let fields = component_fields(component, &kinds);
+            self.write_line(writer, &fields, b'\t', component.raw.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes one row of fields joined by `delimiter`.
+    fn write_line(
+        &self,
+        writer: &mut impl Write,
+        fields: &[String],
+        delimiter: u8,
+        raw: Option<&RawLine>,
+    ) -> std::io::Result<()> {
+        writer.write_all(&encode_line(self.encoding, fields, delimiter, raw))?;
+        writer.write_all(LineEnding::CrLf.as_bytes())
+    }
 }
 
 /// A fully parsed PCB repair file, containing both content and description.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParsedPcbRepairFile {
     /// The parsed content of the file.
     pub content: Content,
@@ -454,9 +1604,379 @@ impl ParsedPcbRepairFile {
             description,
         })
     }
+
+    /// Parses a decoded PCB repair file into a structured format, decoding
+    /// the text in both the content and the description with the given
+    /// encoding instead of detecting it.
+    ///
+    /// # Arguments
+    ///
+    /// * `decoded` - The decoded file data.
+    /// * `encoding` - The encoding of the text fields.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `ParsedPcbRepairFile` or an error.
+    pub fn from_decoded_with_encoding(
+        decoded: &DecodedPcbRepairFile,
+        encoding: TextEncoding,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = Content::from_bytes_with_encoding(decoded.content.as_slice(), encoding)?;
+        let description =
+            Description::from_bytes_with_encoding(decoded.description.as_slice(), encoding)?;
+
+        Ok(Self {
+            content,
+            description,
+        })
+    }
 }
 
 fn parse_decimal(s: &[u8]) -> Result<Decimal, Box<dyn std::error::Error>> {
     let s = String::from_utf8_lossy(s).to_string().replace(',', ".");
     Decimal::from_str(s.as_str()).map_err(|e| e.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_description_crlf() {
+        let data = b"X570|1.00|X570-PRO|1.00A|60MB0000-MB0A01\r\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\r\n\
+01234-00000000\tRES 10K 1% 0402\t2\tR1 R2\t\r\n";
+        let description = Description::from_bytes(data).unwrap();
+        assert_eq!(description.board_model, "X570");
+        assert_eq!(description.part_number, "60MB0000-MB0A01");
+        assert_eq!(description.components.len(), 1);
+        assert_eq!(description.components[0].quantity, Some(2));
+        assert_eq!(description.components[0].location, ["R1", "R2"]);
+        assert!(description.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_description_lf_with_extra_columns() {
+        let data = b"B450M|1.01|B450M-HDV|1.01|90-MXB8A0-A0UAYZ\n\
+\n\
+Item\tLocation\tQty\tPart Number\tDescription\tVendor\n\
+1\tC1,C2\tN/A\t02-000001\tCAP 22UF 6.3V X5R 0603\tACME\n\
+2\tFB1\t1.000\t02-000002\n";
+        let description = Description::from_bytes(data).unwrap();
+        assert_eq!(description.columns.len(), 6);
+        assert_eq!(description.components.len(), 2);
+
+        let cap = &description.components[0];
+        assert_eq!(cap.part_number, "02-000001");
+        assert_eq!(cap.description, "CAP 22UF 6.3V X5R 0603");
+        assert_eq!(cap.quantity, None);
+        assert_eq!(cap.location, ["C1", "C2"]);
+        assert_eq!(
+            cap.extra,
+            BTreeMap::from([(0, "1".to_string()), (5, "ACME".to_string())])
+        );
+
+        let ferrite = &description.components[1];
+        assert_eq!(ferrite.quantity, Some(1));
+        assert_eq!(description.warnings.len(), 1);
+        assert_eq!(description.warnings[0].line, 5);
+    }
+
+    #[test]
+    fn test_text_encoding_detect() {
+        for (encoding, text) in [
+            (TextEncoding::Gbk, "电阻 10K 1% 0402"),
+            (TextEncoding::Big5, "電阻 10K 1% 0402 晶片電容"),
+            (TextEncoding::ShiftJis, "チップ抵抗 10K 1% 0402"),
+        ] {
+            let bytes = encoding.encode(text);
+            assert_eq!(TextEncoding::detect(&bytes), encoding, "{}", text);
+            assert_eq!(encoding.decode(&bytes), text);
+        }
+        assert_eq!(TextEncoding::detect(b"GND"), TextEncoding::Utf8);
+    }
+
+    #[test]
+    fn test_description_gbk() {
+        let mut data = b"X570|1.00|X570-PRO|1.00A|60MB0000-MB0A01\r\n".to_vec();
+        data.extend(b"PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\r\n");
+        data.extend(TextEncoding::Gbk.encode("01234-00000000\t贴片电阻 10K\t1\tR1\t\r\n"));
+        let description = Description::from_bytes(&data).unwrap();
+        assert_eq!(description.encoding, TextEncoding::Gbk);
+        assert_eq!(description.components[0].description, "贴片电阻 10K");
+    }
+
+    #[test]
+    fn test_undecodable_bytes_round_trip() {
+        // 0xFF isn't valid in UTF-8, so it's decoded as U+FFFD
+        let content = b"A!UNIT!mils!\r\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\r\n\
+S!NET\xFF!R1!1!1!100!200!!10!\r\n";
+        let parsed = Content::from_bytes_with_encoding(content, TextEncoding::Utf8).unwrap();
+        assert_eq!(parsed.pins[0].net_name, "NET\u{FFFD}");
+        let mut written = Vec::new();
+        parsed.write_to(&mut written).unwrap();
+        assert_eq!(written, content);
+
+        let description = b"B\xFF|1|B|1|PN\r\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\r\n\
+1\tRES \xFF\t1\tR1\t\r\n";
+        let mut parsed =
+            Description::from_bytes_with_encoding(description, TextEncoding::Utf8).unwrap();
+        let mut written = Vec::new();
+        parsed.write_to(&mut written).unwrap();
+        assert_eq!(written, description);
+
+        // Edited fields are written from their new text
+        parsed.components[0].description = "RES 10K".into();
+        let mut written = Vec::new();
+        parsed.write_to(&mut written).unwrap();
+        assert!(written.ends_with(b"1\tRES 10K\t1\tR1\t\r\n"));
+    }
+
+    const CONTENT: &[u8] = b"A!UNIT!mils!\r\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\r\n\
+S!R1!1!R0402!NO!90!\r\n\
+S!U1!1!QFN32!YES!0!\r\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\r\n\
+S!+3V3!R1!1!1!100,50!200!!10!\r\n\
+A!LOGOInfo!ASUS!\r\n\
+S!GND!R1!2!2!140,50!200!!10!\r\n\
+A!VIAID!NET_NAME!VIA_X!VIA_Y!\r\n\
+S!1!GND!120!220!\r\n\
+A!TESTVIA!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!VIA_X!VIA_Y!TEST_POINT!RADIUS!\r\n\
+S!TP1!GND!!!!120!220!T!5!\r\n";
+
+    #[test]
+    fn test_content_round_trip() {
+        let content = Content::from_bytes(CONTENT).unwrap();
+        assert_eq!(content.symbols.len(), 2);
+        assert_eq!(content.pins.len(), 2);
+        assert_eq!(content.pins[0].pin_x, Decimal::new(10050, 2));
+        assert_eq!(content.vias.len(), 1);
+        assert_eq!(content.vias[0].net_name, "GND");
+        assert_eq!(content.vias[0].via_y, Decimal::new(220, 0));
+
+        let mut written = Vec::new();
+        content.write_to(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&written),
+            String::from_utf8_lossy(CONTENT)
+        );
+    }
+
+    #[test]
+    fn test_content_round_trip_irregular() {
+        let content = b"A!UNIT!mils!\n\
+\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!R1!1!R0402!no!90!\n\
+S!R2!1!R0402!!0\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!GND!R1!1!1!+5!.5!!007!\n\
+S!GND!R1!2!2!7.50!0!!10!EXTRA\n\
+\n\
+A!NET_NAME!REFDES!PIN_NUMBER!PIN_NAME!PIN_X!PIN_Y!TEST_POINT!RADIUS!\n\
+S!GND!R2!1!1!1,5!0!!10!\n\
+\n";
+        let mut parsed = Content::from_bytes(content).unwrap();
+        assert!(!parsed.symbols[0].sym_mirror);
+        assert_eq!(parsed.pins[0].pin_x, Decimal::new(5, 0));
+        assert_eq!(parsed.pins[0].pin_y, Decimal::new(5, 1));
+        assert_eq!(parsed.pins[0].radius, Decimal::new(7, 0));
+        assert_eq!(parsed.pins[2].pin_x, Decimal::new(15, 1));
+
+        let mut written = Vec::new();
+        parsed.write_to(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&written),
+            String::from_utf8_lossy(content)
+        );
+
+        // Edited values are written in the canonical form
+        parsed.symbols[0].sym_mirror = true;
+        parsed.pins[0].pin_x = Decimal::new(6, 0);
+        let mut written = Vec::new();
+        parsed.write_to(&mut written).unwrap();
+        let written = String::from_utf8_lossy(&written);
+        assert!(written.contains("S!R1!1!R0402!YES!90!\n"));
+        assert!(written.contains("S!GND!R1!1!1!6!.5!!007!\n"));
+    }
+
+    #[test]
+    fn test_content_malformed_via() {
+        let content = b"A!UNIT!mils!\n\
+A!VIAID!NET_NAME!VIA_X!VIA_Y!RADIUS!\n\
+S!1!GND!120!220!!\n\
+S!2!GND!!220!5!\n\
+S!3!GND!140!220!x!\n\
+S!4!GND!160!220!5!\n";
+        let parsed = Content::from_bytes(content).unwrap();
+        let ids: Vec<&str> = parsed.vias.iter().map(|v| v.via_id.as_str()).collect();
+        assert_eq!(ids, ["1", "4"]);
+        assert_eq!(parsed.vias[0].radius, None);
+        let lines: Vec<u64> = parsed.warnings.iter().map(|w| w.line).collect();
+        assert_eq!(lines, [4, 5]);
+
+        let mut written = Vec::new();
+        parsed.write_to(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&written),
+            String::from_utf8_lossy(content)
+        );
+    }
+
+    #[test]
+    fn test_content_write_added_records() {
+        let mut content = Content::from_bytes(CONTENT).unwrap();
+        content.symbols.push(Symbol {
+            refdes: "C1".into(),
+            comp_insertion_code: 1,
+            sym_name: "C0402".into(),
+            sym_mirror: false,
+            sym_rotate: 0,
+            raw: None,
+        });
+        content.sections.retain(|s| s.kind != SectionKind::TestVias);
+
+        let mut written = Vec::new();
+        content.write_to(&mut written).unwrap();
+        let reparsed = Content::from_bytes(&written).unwrap();
+        assert_eq!(reparsed.symbols.len(), 3);
+        assert_eq!(reparsed.symbols[2].refdes, "C1");
+        assert_eq!(reparsed.pins.len(), 2);
+        assert_eq!(reparsed.testvias.len(), 1);
+    }
+
+    #[test]
+    fn test_content_write_reordered_records() {
+        let mut content = Content::from_bytes(CONTENT).unwrap();
+        // Original text follows its record, wherever the record goes
+        content.pins.swap(0, 1);
+        content.pins.insert(
+            0,
+            Pin {
+                net_name: "VCC".into(),
+                refdes: "U1".into(),
+                pin_number: "1".into(),
+                pin_name: "1".into(),
+                pin_x: Decimal::new(15, 1),
+                pin_y: Decimal::ZERO,
+                test_point: String::new(),
+                radius: Decimal::new(10, 0),
+                raw: None,
+            },
+        );
+
+        let mut written = Vec::new();
+        content.write_to(&mut written).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert!(written.contains(
+            "RADIUS!\r\n\
+S!VCC!U1!1!1!1.5!0!!10!\r\n\
+A!LOGOInfo!ASUS!\r\n\
+S!GND!R1!2!2!140,50!200!!10!\r\n\
+S!+3V3!R1!1!1!100,50!200!!10!\r\n"
+        ));
+    }
+
+    #[test]
+    fn test_content_short_record() {
+        let content = b"A!UNIT!mils!\n\
+A!REFDES!COMP_INSERTION_CODE!SYM_NAME!SYM_MIRROR!SYM_ROTATE!\n\
+S!R1!1!R0402\n";
+        let error = Content::from_bytes(content).unwrap_err();
+        assert_eq!(error.to_string(), "Line 3: record has 4 of 6 fields");
+        assert!(Content::from_bytes(b"A\n").is_err());
+    }
+
+    #[test]
+    fn test_description_round_trip() {
+        let data = b"Z790|1.02|Z790-A|1.02X|60MB1CK0-MB0A10\r\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\tNOTE\r\n\
+01234-00000000\tRES 10K 1% 0402\t2\tR1 R2\t\t\r\n\
+01234-00000001\tCAP 22UF 6.3V X5R 0603\t\tC1\t01234-00000002\tDNP\r\n";
+        let description = Description::from_bytes(data).unwrap();
+
+        let mut written = Vec::new();
+        description.write_to(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&written),
+            String::from_utf8_lossy(data)
+        );
+    }
+
+    #[test]
+    fn test_description_round_trip_irregular() {
+        let data = b"Z790|1.02|Z790-A|1.02X|60MB1CK0-MB0A10|EXTRA\r\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tNOTE\tNOTE\r\n\
+01234-00000000\tRES 10K 1% 0402\tN/A\tR1,R2\tFIRST\tSECOND\r\n";
+        let mut description = Description::from_bytes(data).unwrap();
+        assert_eq!(description.header_extra, ["EXTRA"]);
+        let component = &description.components[0];
+        assert_eq!(component.quantity, None);
+        assert_eq!(component.extra[&4], "FIRST");
+        assert_eq!(component.extra[&5], "SECOND");
+
+        let mut written = Vec::new();
+        description.write_to(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&written),
+            String::from_utf8_lossy(data)
+        );
+
+        // A new quantity replaces the original text
+        description.components[0].quantity = Some(2);
+        let mut written = Vec::new();
+        description.write_to(&mut written).unwrap();
+        assert!(written.ends_with(b"0402\t2\tR1,R2\tFIRST\tSECOND\r\n"));
+    }
+
+    #[test]
+    fn test_description_write_reordered_components() {
+        let data = b"Z790|1.02|Z790-A|1.02X|60MB1CK0-MB0A10\r\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\r\n\
+01234-00000000\tRES 10K 1% 0402\tN/A\tR1,R2\t\r\n\
+01234-00000001\tCAP 22UF 6.3V X5R 0603\t1.000\tC1\t\r\n\
+\t\t\t\t\r\n\
+01234-00000002\tCAP 1UF 0402\t+1\tC2\r\n";
+        let mut description = Description::from_bytes(data).unwrap();
+        assert_eq!(description.components.len(), 3);
+
+        // Original text follows its component
+        description.components.swap(0, 2);
+        description.components.remove(1);
+        let mut written = Vec::new();
+        description.write_to(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&written),
+            "Z790|1.02|Z790-A|1.02X|60MB1CK0-MB0A10\r\n\
+PART NUMBER\tDESCRIPTION\tQTY\tLOCATION\tPART NUMBER2\r\n\
+01234-00000002\tCAP 1UF 0402\t+1\tC2\r\n\
+01234-00000000\tRES 10K 1% 0402\tN/A\tR1,R2\t\r\n"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_content_serde() {
+        let content = Content::from_bytes(CONTENT).unwrap();
+        let json = serde_json::to_value(&content).unwrap();
+        assert_eq!(json["pins"][0]["pin_x"], "100.50");
+        assert_eq!(json["units"], "Mils");
+
+        let schema: serde_json::Value = serde_json::from_str(crate::JSON_SCHEMA).unwrap();
+        assert_eq!(
+            schema["properties"]["schema_version"]["const"],
+            crate::JSON_SCHEMA_VERSION
+        );
+        let content_properties = schema["$defs"]["Content"]["properties"]
+            .as_object()
+            .unwrap();
+        let json_fields = json.as_object().unwrap();
+        assert!(json_fields.keys().eq(content_properties.keys()));
+
+        let deserialized: Content = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized.pins[0].pin_x, content.pins[0].pin_x);
+    }
+}